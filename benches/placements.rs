@@ -0,0 +1,163 @@
+//! Compares the collision checks and line clears of the bitmask board
+//! with the array of blocks the game used to keep, by dropping the same
+//! pieces on both.
+//! Run it with `cargo bench --features full_redraw`.
+use fourtris::BitBoard;
+use fourtris::game::MAX_BOARD_WIDTH;
+use fourtris::game_renderer::TetriminoType;
+use fourtris::MAX_BOARD_HEIGHT;
+
+use std::hint::black_box;
+use std::ops::Range;
+use std::time::Instant;
+
+/// The number of pieces placed on each board per run.
+const PLACEMENTS : u32 = 2_000_000;
+
+/// The number of runs on each board. The fastest one is reported,
+/// as it's the one that was interrupted the least.
+const RUNS : u32 = 10;
+
+/// The rows of the board, the 20 visible ones and the buffer zone.
+const BOARD_HEIGHT : usize = 40;
+
+/// The row pieces are dropped from, just above the visible rows.
+const SPAWN_ROW : i32 = 20;
+
+/// What a placement needs from a board, so both boards run the same code.
+trait Cells {
+    fn with_width(width: usize) -> Self;
+
+    /// Returns `true` if a cell is outside the walls or floor, or is filled.
+    fn is_blocked(&self, x: i32, y: i32) -> bool;
+
+    /// Fills a cell on the board.
+    fn fill(&mut self, x: usize, y: usize);
+
+    /// Removes the full rows in `y_range`, returning how many there were.
+    fn clear_lines(&mut self, y_range: Range<usize>) -> u32;
+}
+
+/// The board as it was before the bitmasks: a block type for every cell,
+/// with the same collision and line clear code.
+struct ArrayBoard {
+    content: [[TetriminoType; MAX_BOARD_WIDTH]; MAX_BOARD_HEIGHT],
+    width: usize,
+    height: usize,
+}
+
+impl Cells for ArrayBoard {
+    fn with_width(width: usize) -> Self {
+        ArrayBoard {
+            content: [[TetriminoType::EmptySpace; MAX_BOARD_WIDTH]; MAX_BOARD_HEIGHT],
+            width,
+            height: BOARD_HEIGHT,
+        }
+    }
+
+    fn is_blocked(&self, x: i32, y: i32) -> bool {
+        if x < 0 || x >= self.width as i32 || y < 0 {
+            true
+        } else if y >= self.height as i32 {
+            false
+        } else {
+            self.content[y as usize][x as usize] != TetriminoType::EmptySpace
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize) {
+        self.content[y][x] = TetriminoType::O;
+    }
+
+    fn clear_lines(&mut self, y_range: Range<usize>) -> u32 {
+        let mut full_rows = [0; 4];
+        let mut len = 0;
+        for y in y_range {
+            let is_completed_line = self.content[y][..self.width].iter().all(|&val| val != TetriminoType::EmptySpace);
+            if is_completed_line && len < full_rows.len() {
+                full_rows[len] = y;
+                len += 1;
+            }
+        }
+
+        for (cleared_so_far, &y) in full_rows[..len].iter().enumerate() {
+            // adjust the y coordinate for the lines already removed
+            let real_y = y - cleared_so_far;
+            // shift all the grid rows above this line down
+            for i in real_y..(self.height - 1) {
+                self.content[i] = self.content[i + 1];
+            }
+            for x in self.content[self.height - 1].iter_mut() {
+                *x = TetriminoType::EmptySpace;
+            }
+        }
+        len as u32
+    }
+}
+
+impl Cells for BitBoard {
+    fn with_width(width: usize) -> Self {
+        BitBoard::new(width, BOARD_HEIGHT)
+    }
+
+    fn is_blocked(&self, x: i32, y: i32) -> bool {
+        BitBoard::is_blocked(self, x, y)
+    }
+
+    fn fill(&mut self, x: usize, y: usize) {
+        BitBoard::fill(self, x, y)
+    }
+
+    fn clear_lines(&mut self, y_range: Range<usize>) -> u32 {
+        let full_rows = self.full_rows(y_range);
+        self.remove_rows(&full_rows);
+        full_rows.len() as u32
+    }
+}
+
+/// Drops an O piece with its left column at `x` from the spawn row,
+/// one row at a time like gravity does, then locks it and clears lines.
+fn drop_o<C: Cells>(board: &mut C, x: i32) -> u32 {
+    let mut y = SPAWN_ROW;
+    while !board.is_blocked(x, y - 1) && !board.is_blocked(x + 1, y - 1) {
+        y -= 1;
+    }
+    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        board.fill((x + dx) as usize, (y + dy) as usize);
+    }
+    board.clear_lines(y as usize..y as usize + 2)
+}
+
+/// Places `PLACEMENTS` O pieces side by side, so every finished row of
+/// them clears two lines. Returns the number of lines cleared.
+fn place_pieces<C: Cells>(width: usize) -> u32 {
+    let mut board = C::with_width(width);
+    let columns = width as u32 / 2;
+    let mut lines = 0;
+    for i in 0..PLACEMENTS {
+        let x = black_box(2 * (i % columns)) as i32;
+        lines += drop_o(&mut board, x);
+    }
+    lines
+}
+
+/// Times `place_pieces` and returns the placements per second.
+fn placements_per_second<C: Cells>(width: usize) -> f64 {
+    let mut fastest = f64::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let lines = place_pieces::<C>(width);
+        fastest = fastest.min(start.elapsed().as_secs_f64());
+        assert_eq!(lines, 2 * PLACEMENTS / (width as u32 / 2));
+    }
+    PLACEMENTS as f64 / fastest
+}
+
+fn main() {
+    for (name, width) in [("standard board", 10), ("wide board", 20)] {
+        let array = placements_per_second::<ArrayBoard>(width);
+        let bits = placements_per_second::<BitBoard>(width);
+        println!("{}: array {:.0} placements/s, bitmask {:.0} placements/s ({:.1}x)",
+                 name, array, bits, bits / array);
+    }
+}
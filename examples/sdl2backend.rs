@@ -9,11 +9,15 @@ use sdl2::rect::Rect;
 use sdl2::ttf::Font;
 
 extern crate rand;
-use rand::Rng;
-use fourtris::game::{Game, GameState, Input};
+use fourtris::game::{Game, GameConfig, GameState, Phase, TopOut, MAX_PREVIEW_LENGTH};
+use fourtris::game::{DEFAULT_BOARD_WIDTH, DEFAULT_VISIBLE_HEIGHT};
+use fourtris::input::{Action, InputEvent};
+use fourtris::randomizer::SevenBag;
+use fourtris::rng::Pcg32;
 use fourtris::game_renderer::{GameRenderer, TetriminoType};
+use fourtris::scoring::LineClear;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::path::Path;
 
 // ---------------------------
@@ -21,34 +25,44 @@ use std::path::Path;
 // ---------------------------
 const PADDING : u32 = 80;
 const BLOCK_WIDTH : u32 = 20;
-const PLAYFIELD_WIDTH : u32 = BLOCK_WIDTH * 10;
-const PLAYFIELD_HEIGHT : u32 = BLOCK_WIDTH * 22;
+const PLAYFIELD_WIDTH : u32 = BLOCK_WIDTH * DEFAULT_BOARD_WIDTH as u32;
+const PLAYFIELD_HEIGHT : u32 = BLOCK_WIDTH * DEFAULT_VISIBLE_HEIGHT as u32;
 const WINDOW_WIDTH : u32 = 2 * PADDING + PLAYFIELD_WIDTH;
 const WINDOW_HEIGHT : u32 = PLAYFIELD_HEIGHT;
-
-
-pub struct Randy {
-    rng: rand::rngs::ThreadRng
-}
-
-impl Randy {
-    pub fn new() -> Randy {
-        let rng = rand::thread_rng();
-        Randy {
-            rng
-        }
-    }
-}
-
-impl Default for Randy {
-    fn default() -> Self {
-        Self::new()
+const PREVIEW_BLOCK_WIDTH : u32 = 10;
+const HOLD_Y : i32 = 80;
+const NEXT_Y : i32 = 80;
+const LINE_CLEAR_Y : i32 = 160;
+const ENTRY_DELAY : u32 = 6;
+const LINE_CLEAR_DELAY : u32 = 20;
+
+
+/// The color used to draw each type of tetrimino.
+fn tetrimino_color(tetrimino_type: TetriminoType) -> Color {
+    match tetrimino_type {
+        TetriminoType::I          => Color::RGB(0, 0, 200),
+        TetriminoType::O          => Color::RGB(0, 200, 0),
+        TetriminoType::J          => Color::RGB(0, 200, 200),
+        TetriminoType::L          => Color::RGB(200, 0, 0),
+        TetriminoType::S          => Color::RGB(200, 0, 200),
+        TetriminoType::Z          => Color::RGB(200, 200, 0),
+        TetriminoType::T          => Color::RGB(100, 200, 100),
+        TetriminoType::EmptySpace => Color::RGB(0, 0, 0),
     }
 }
 
-impl fourtris::rng::Rng for Randy {
-    fn next(&mut self) -> usize {
-        self.rng.gen_range(0..7)
+/// The cells a piece occupies in its spawn orientation, used to draw
+/// pieces outside of the playfield. y grows downwards.
+fn preview_blocks(tetrimino_type: TetriminoType) -> [(i32, i32); 4] {
+    match tetrimino_type {
+        TetriminoType::I => [(0, 1), (1, 1), (2, 1), (3, 1)],
+        TetriminoType::O => [(1, 0), (2, 0), (1, 1), (2, 1)],
+        TetriminoType::J => [(0, 0), (0, 1), (1, 1), (2, 1)],
+        TetriminoType::L => [(2, 0), (0, 1), (1, 1), (2, 1)],
+        TetriminoType::S => [(1, 0), (2, 0), (0, 1), (1, 1)],
+        TetriminoType::Z => [(0, 0), (1, 0), (1, 1), (2, 1)],
+        TetriminoType::T => [(1, 0), (0, 1), (1, 1), (2, 1)],
+        TetriminoType::EmptySpace => [(0, 0); 4],
     }
 }
 
@@ -64,6 +78,34 @@ impl Sdl2Backend<'_, '_> {
             font,
         }
     }
+
+    /// Covers the playfield while the game is paused.
+    fn draw_paused(&mut self) {
+        self.canvas.set_draw_color(Color::RGB(0, 0, 0));
+        self.canvas.fill_rect(Rect::new(PADDING as i32, 0, PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT)).unwrap();
+
+        let texture_creator = self.canvas.texture_creator();
+        let render_paused_shaded = self.font.render("PAUSED").
+            shaded(Color::RGB(255, 255, 255), Color::RGB(0, 0, 0)).unwrap();
+        let paused_texture = Texture::from_surface(&render_paused_shaded, &texture_creator).unwrap();
+        let paused_rect = Rect::new((PADDING + (PLAYFIELD_WIDTH - render_paused_shaded.width()) / 2) as i32,
+                                    ((PLAYFIELD_HEIGHT - render_paused_shaded.height()) / 2) as i32,
+                                    render_paused_shaded.width(),
+                                    render_paused_shaded.height());
+        self.canvas.copy(&paused_texture, None, Some(paused_rect)).unwrap();
+    }
+
+    /// Draws a small version of a piece with its top left corner at (x, y).
+    fn draw_preview(&mut self, x: i32, y: i32, tetrimino_type: TetriminoType) {
+        self.canvas.set_draw_color(tetrimino_color(tetrimino_type));
+        for (bx, by) in preview_blocks(tetrimino_type).iter() {
+            let rect = Rect::new(x + bx * PREVIEW_BLOCK_WIDTH as i32,
+                                 y + by * PREVIEW_BLOCK_WIDTH as i32,
+                                 PREVIEW_BLOCK_WIDTH,
+                                 PREVIEW_BLOCK_WIDTH);
+            self.canvas.fill_rect(rect).unwrap();
+        }
+    }
 }
 
 impl GameRenderer for Sdl2Backend<'_, '_> {
@@ -84,32 +126,7 @@ impl GameRenderer for Sdl2Backend<'_, '_> {
     }
 
     fn draw_block(&mut self, x: u8, y: u8, tetrimino_type: TetriminoType) {
-        match tetrimino_type {
-            TetriminoType::I => {
-                self.canvas.set_draw_color(Color::RGB(0, 0, 200));
-            },
-            TetriminoType::O => {
-                self.canvas.set_draw_color(Color::RGB(0, 200, 0));
-            },
-            TetriminoType::J => {
-                self.canvas.set_draw_color(Color::RGB(0, 200, 200));
-            },
-            TetriminoType::L => {
-                self.canvas.set_draw_color(Color::RGB(200, 0, 0));
-            },
-            TetriminoType::S => {
-                self.canvas.set_draw_color(Color::RGB(200, 0, 200));
-            },
-            TetriminoType::Z => {
-                self.canvas.set_draw_color(Color::RGB(200, 200, 0));
-            },
-            TetriminoType::T => {
-                self.canvas.set_draw_color(Color::RGB(100, 200, 100));
-            },
-            TetriminoType::EmptySpace => {
-                self.canvas.set_draw_color(Color::RGB(0, 0, 0));
-            },
-        };
+        self.canvas.set_draw_color(tetrimino_color(tetrimino_type));
 
         let real_x = x as i32 * BLOCK_WIDTH  as i32 + PADDING as i32;
         let real_y = y as i32 * BLOCK_WIDTH  as i32;
@@ -121,6 +138,20 @@ impl GameRenderer for Sdl2Backend<'_, '_> {
         self.canvas.fill_rect(rect).unwrap();
     }
 
+    fn draw_ghost_block(&mut self, x: u8, y: u8, tetrimino_type: TetriminoType) {
+        self.canvas.set_draw_color(tetrimino_color(tetrimino_type));
+
+        // only draw the outline so the ghost can't be confused with the stack
+        let real_x = x as i32 * BLOCK_WIDTH  as i32 + PADDING as i32;
+        let real_y = y as i32 * BLOCK_WIDTH  as i32;
+        let rect = Rect::new(real_x + 1,
+                             real_y + 1,
+                             BLOCK_WIDTH - 2,
+                             BLOCK_WIDTH - 2);
+
+        self.canvas.draw_rect(rect).unwrap();
+    }
+
     // I don't feel like implementing these, but here is where they really belong
     fn draw_score(&mut self, score: u32) {
         // create a texture for the numerical score
@@ -182,6 +213,114 @@ impl GameRenderer for Sdl2Backend<'_, '_> {
                                          render_level_value_shaded.height());
         self.canvas.copy(&level_value_texture, None, Some(level_value_rect)).unwrap();
     }
+
+    fn draw_hold(&mut self, piece_type: Option<TetriminoType>) {
+        let text_foreground_color = Color::RGB(255, 0, 0);
+        let text_background_color = Color::RGB(255, 255, 255);
+        let texture_creator = self.canvas.texture_creator();
+
+        // create hold texture
+        let render_hold_string_shaded = self.font.render("HOLD").
+            shaded(text_foreground_color, text_background_color).unwrap();
+        let hold_string_texture = Texture::from_surface(&render_hold_string_shaded, &texture_creator).unwrap();
+        let hold_string_rect = Rect::new(5,
+                                         HOLD_Y,
+                                         render_hold_string_shaded.width(),
+                                         render_hold_string_shaded.height());
+        self.canvas.copy(&hold_string_texture, None, Some(hold_string_rect)).unwrap();
+
+        // clear the previously held piece
+        let preview_y = HOLD_Y + hold_string_rect.height() as i32 + 5;
+        self.canvas.set_draw_color(text_background_color);
+        self.canvas.fill_rect(Rect::new(0, preview_y, PADDING, 2 * PREVIEW_BLOCK_WIDTH)).unwrap();
+
+        if let Some(piece_type) = piece_type {
+            let x_pos = (PADDING - 4 * PREVIEW_BLOCK_WIDTH) / 2;
+            self.draw_preview(x_pos as i32, preview_y, piece_type);
+        }
+    }
+
+    fn draw_next(&mut self, piece_types: &[TetriminoType]) {
+        let text_foreground_color = Color::RGB(255, 0, 0);
+        let text_background_color = Color::RGB(255, 255, 255);
+        let texture_creator = self.canvas.texture_creator();
+
+        // create next texture
+        let render_next_string_shaded = self.font.render("NEXT").
+            shaded(text_foreground_color, text_background_color).unwrap();
+        let next_string_texture = Texture::from_surface(&render_next_string_shaded, &texture_creator).unwrap();
+        let next_string_rect = Rect::new((PADDING + PLAYFIELD_WIDTH + 5) as i32,
+                                         NEXT_Y,
+                                         render_next_string_shaded.width(),
+                                         render_next_string_shaded.height());
+        self.canvas.copy(&next_string_texture, None, Some(next_string_rect)).unwrap();
+
+        // clear the previous queue
+        let preview_y = NEXT_Y + next_string_rect.height() as i32 + 5;
+        let preview_spacing = 3 * PREVIEW_BLOCK_WIDTH;
+        self.canvas.set_draw_color(text_background_color);
+        self.canvas.fill_rect(Rect::new((PADDING + PLAYFIELD_WIDTH) as i32,
+                                        preview_y,
+                                        PADDING,
+                                        MAX_PREVIEW_LENGTH as u32 * preview_spacing)).unwrap();
+
+        // draw the queue from top to bottom
+        let x_pos = PADDING + PLAYFIELD_WIDTH + (PADDING - 4 * PREVIEW_BLOCK_WIDTH) / 2;
+        for (i, &piece_type) in piece_types.iter().enumerate() {
+            let y_pos = preview_y + (i as u32 * preview_spacing) as i32;
+            self.draw_preview(x_pos as i32, y_pos, piece_type);
+        }
+    }
+
+    fn draw_line_clear(&mut self, line_clear: Option<LineClear>) {
+        let text_foreground_color = Color::RGB(255, 0, 0);
+        let text_background_color = Color::RGB(255, 255, 255);
+        let texture_creator = self.canvas.texture_creator();
+
+        // clear the previous announcement
+        self.canvas.set_draw_color(text_background_color);
+        self.canvas.fill_rect(Rect::new(0, LINE_CLEAR_Y, PADDING, PLAYFIELD_HEIGHT - LINE_CLEAR_Y as u32)).unwrap();
+
+        let line_clear = match line_clear {
+            Some(line_clear) => line_clear,
+            None => return,
+        };
+
+        // the side panel is narrow, so put each word on its own line
+        let combo = format!("COMBO {}", line_clear.combo);
+        let words = line_clear.name().split(' ').
+            chain(line_clear.back_to_back.then_some("B2B")).
+            chain((line_clear.combo > 0).then_some(combo.as_str())).
+            chain(line_clear.perfect_clear.then_some("PERFECT")).
+            chain(line_clear.perfect_clear.then_some("CLEAR"));
+
+        let mut y_pos = LINE_CLEAR_Y;
+        for word in words {
+            let render_word_shaded = self.font.render(word).
+                shaded(text_foreground_color, text_background_color).unwrap();
+            let word_texture = Texture::from_surface(&render_word_shaded, &texture_creator).unwrap();
+            let word_rect = Rect::new(5,
+                                      y_pos,
+                                      render_word_shaded.width(),
+                                      render_word_shaded.height());
+            self.canvas.copy(&word_texture, None, Some(word_rect)).unwrap();
+            y_pos += word_rect.height() as i32;
+        }
+    }
+}
+
+/// Maps the keyboard controls to the game's actions.
+fn key_action(keycode: Keycode) -> Option<Action> {
+    match keycode {
+        Keycode::Left  => Some(Action::Left),
+        Keycode::Right => Some(Action::Right),
+        Keycode::Down  => Some(Action::Down),
+        Keycode::Q     => Some(Action::CcwRotate),
+        Keycode::W     => Some(Action::CwRotate),
+        Keycode::Space => Some(Action::HardDrop),
+        Keycode::C     => Some(Action::Hold),
+        _ => None,
+    }
 }
 
 fn main() {
@@ -199,11 +338,21 @@ fn main() {
     let font_path = Path::new("Raleway-Bold.ttf");
     let font = sdl_ttf_context.load_font(font_path, 20).unwrap();
 
-    let mut randy = Randy::new();
+    // pass a seed on the command line to replay the same pieces
+    let seed = std::env::args().nth(1).
+        and_then(|arg| arg.parse().ok()).
+        unwrap_or_else(rand::random::<u64>);
+    println!("Seed: {}", seed);
+    let mut randomizer = SevenBag::new(Pcg32::new(seed));
     let mut event_pump = sdl_context.event_pump().unwrap();
-    let mut game = Game::new(&mut randy);
-    let mut input : Input = Default::default();
-
+    let config = GameConfig {
+        are: ENTRY_DELAY,
+        line_clear_delay: LINE_CLEAR_DELAY,
+        .. Default::default()
+    };
+    let mut game = Game::with_config(&mut randomizer, config);
+
+    let mut last_update = Instant::now();
     'playing: loop {
         // handle events
         for event in event_pump.poll_iter() {
@@ -212,34 +361,44 @@ fn main() {
                 Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
                     break 'playing
                 },
-                Event::KeyDown { keycode: Some(keycode), .. } => {
-                    match keycode {
-                        Keycode::Left  => input.left       = true,
-                        Keycode::Right => input.right      = true,
-                        Keycode::Down  => input.down       = true,
-                        Keycode::Q     => input.ccw_rotate = true,
-                        Keycode::W     => input.cw_rotate  = true,
-                        _ => {},
+                Event::KeyDown { keycode: Some(Keycode::P), repeat: false, .. } => {
+                    if game.state() == GameState::Paused {
+                        game.resume();
+                    } else {
+                        game.pause();
                     }
                 },
-                Event::KeyUp { keycode: Some(keycode), .. } => {
-                    match keycode {
-                        Keycode::Left  => input.left       = false,
-                        Keycode::Right => input.right      = false,
-                        Keycode::Down  => input.down       = false,
-                        Keycode::Q     => input.ccw_rotate = false,
-                        Keycode::W     => input.cw_rotate  = false,
-                        _ => {},
+                Event::KeyDown { keycode: Some(Keycode::R), repeat: false, .. } => {
+                    let seed = rand::random::<u64>();
+                    println!("Seed: {}", seed);
+                    randomizer = SevenBag::new(Pcg32::new(seed));
+                    game.reset(&mut randomizer);
+                },
+                // ignore key repeats, the game takes care of repeating moves
+                Event::KeyDown { keycode: Some(keycode), repeat: false, timestamp, .. } => {
+                    if let Some(action) = key_action(keycode) {
+                        game.push_input_event(InputEvent { action, pressed: true, timestamp });
+                    }
+                },
+                Event::KeyUp { keycode: Some(keycode), timestamp, .. } => {
+                    if let Some(action) = key_action(keycode) {
+                        game.push_input_event(InputEvent { action, pressed: false, timestamp });
                     }
                 },
                 _ => {},
             }
         }
 
-        // run the game loop
-        let state = game.run_loop(&input, &mut randy);
+        // run the game for however long it's been since the last update
+        let now = Instant::now();
+        let state = game.update(now - last_update, &mut randomizer);
+        last_update = now;
 
-        if state == GameState::GameOver {
+        if let GameState::GameOver(top_out) = state {
+            match top_out {
+                TopOut::BlockOut => println!("BLOCK OUT!"),
+                TopOut::LockOut => println!("LOCK OUT!"),
+            }
             println!("GAME OVER MAN!");
             println!("You made it to level {}", game.level());
             println!("Final score: {}", game.score());
@@ -254,11 +413,30 @@ fn main() {
 
             let mut backend = Sdl2Backend::new(&mut canvas, &font);
             game.draw(&mut backend);
+
+            // hide the playfield so the pause can't be used to plan ahead
+            if state == GameState::Paused {
+                backend.draw_paused();
+            }
+        }
+
+        // fade the cleared rows to white before the stack collapses
+        if game.phase() == Phase::LineClear && state != GameState::Paused {
+            let remaining = game.delay_remaining().unwrap_or(0);
+            let brightness = (255 * (LINE_CLEAR_DELAY - remaining) / LINE_CLEAR_DELAY) as u8;
+            canvas.set_draw_color(Color::RGB(brightness, brightness, brightness));
+            for y in game.rows_being_cleared() {
+                let row = Rect::new(PADDING as i32,
+                                    y as i32 * BLOCK_WIDTH as i32,
+                                    PLAYFIELD_WIDTH,
+                                    BLOCK_WIDTH);
+                canvas.fill_rect(row).unwrap();
+            }
         }
 
         canvas.present();
-        // sleep between frames
-        // 16 milliseconds is ~ 60 fps
-        std::thread::sleep(Duration::from_millis(16));
+        // sleep between frames, the game keeps the right speed
+        // however long this actually takes
+        std::thread::sleep(Duration::from_millis(1));
     }
 }
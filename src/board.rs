@@ -1,43 +1,257 @@
 use crate::coord::Coord;
 use crate::pieces::Piece;
-use crate::pieces::PieceType;
 use crate::game_renderer::TetriminoType;
 
 use core::ops::Range;
 
-const BOARD_WIDTH: usize  =  10;
-const BOARD_HEIGHT: usize  =  22;
+/// The widest board a game can be played on.
+pub const MAX_BOARD_WIDTH: usize = 20;
+
+/// The most rows of the board the player can see.
+pub const MAX_VISIBLE_HEIGHT: usize = 40;
+
+/// The size of the standard board.
+pub const DEFAULT_BOARD_WIDTH: usize = 10;
+pub const DEFAULT_VISIBLE_HEIGHT: usize = 20;
+
+/// The smallest board still has room for an I piece to spawn and rotate.
+const MIN_BOARD_SIZE: usize = 4;
+
+/// The number of rows above the visible ones. This is a buffer zone
+/// that pieces spawn into and the stack can grow into.
+const BUFFER_HEIGHT: usize = 20;
+
+/// The most rows a board can have, including the buffer zone.
+pub const MAX_BOARD_HEIGHT: usize = MAX_VISIBLE_HEIGHT + BUFFER_HEIGHT;
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// The full rows on the board, in order from the bottom up.
+/// A piece can only fill 4 rows at once.
+pub struct FullRows {
+    rows: [usize; 4],
+    len: usize,
+}
+
+impl FullRows {
+    /// Returns the y-values of the full rows.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.rows[..self.len]
+    }
+
+    /// Returns the number of full rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no full rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// One row of the board, with bit x set when column x is filled.
+/// 32 bits leaves room for the widest board.
+type Row = u32;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Which cells of the board are filled, one bit per cell. This is all
+/// that collisions and line clears need, and it's cheap to copy, so a bot
+/// can take one from `BoardView::bits` and try out placements on it.
+/// There are no colours, those are only kept by the game for drawing.
+/// x counts columns from the left and y counts rows up from the floor.
+pub struct BitBoard {
+    rows: [Row; MAX_BOARD_HEIGHT],
+    /// The number of columns.
+    width: usize,
+    /// The number of rows, including the buffer zone.
+    height: usize,
+    /// A row with every column filled.
+    full_row: Row,
+}
+
+impl BitBoard {
+    /// Makes an empty board with the given number of columns and rows,
+    /// including any buffer zone. They are clamped to the range
+    /// 4..=MAX_BOARD_WIDTH and 4..=MAX_BOARD_HEIGHT.
+    pub fn new(width: usize, height: usize) -> BitBoard {
+        let width = width.clamp(MIN_BOARD_SIZE, MAX_BOARD_WIDTH);
+        BitBoard {
+            rows: [0; MAX_BOARD_HEIGHT],
+            width,
+            height: height.clamp(MIN_BOARD_SIZE, MAX_BOARD_HEIGHT),
+            full_row: (1 << width) - 1,
+        }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows, including the buffer zone.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the cell is filled. Cells off the board are empty.
+    pub fn is_filled(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.is_filled_unchecked(x, y)
+    }
+
+    /// Returns `true` if the cell is filled. The cell must be on the board.
+    fn is_filled_unchecked(&self, x: usize, y: usize) -> bool {
+        self.rows[y] & (1 << x) != 0
+    }
+
+    /// Returns a row as a bitmask, with bit x set when column x is filled.
+    /// Rows off the board are empty.
+    pub fn row(&self, y: usize) -> u32 {
+        if y < self.height { self.rows[y] } else { 0 }
+    }
+
+    /// Returns `true` if a cell is outside the walls or floor, or
+    /// is already filled. Cells above the board are empty.
+    pub fn is_blocked(&self, x: i32, y: i32) -> bool {
+        if x < 0 || x >= self.width as i32 || y < 0 {
+            true
+        } else if y >= self.height as i32 {
+            false
+        } else {
+            self.is_filled_unchecked(x as usize, y as usize)
+        }
+    }
+
+    /// Fills a cell.
+    ///
+    /// # Panics
+    /// Panics if the cell is off the board.
+    pub fn fill(&mut self, x: usize, y: usize) {
+        assert!(x < self.width && y < self.height, "Invalid x or y coordinate ({},{})", x, y);
+        self.rows[y] |= 1 << x;
+    }
+
+    // THIS FUNCTION SHOULD ONLY BE CALLED AFTER VERIFYING THAT
+    // THE COORDINATES ARE WITHIN THE BOARD SIZE
+    /// Returns `true` if any of the tetriminos in a piece are already
+    /// occupied in the board.
+    pub(crate) fn is_occupied(&self, coords: &[Coord; 4]) -> bool {
+        coords.iter().any(|&c| c.y >= 0 && self.is_filled_unchecked(c.x as usize, c.y as usize))
+    }
+
+    /// Fills the cells of a piece. The piece must be on the board.
+    pub(crate) fn add_piece(&mut self, piece: &Piece) {
+        for c in piece.position.iter() {
+            self.rows[c.y as usize] |= 1 << c.x;
+        }
+    }
+
+    /// Finds the full lines within the specified y-range,
+    /// from the bottom up. Only the first 4 are found, as that's
+    /// as many as a piece can fill.
+    pub fn full_rows(&self, y_range: Range<usize>) -> FullRows {
+        let mut full_rows = FullRows::default();
+        for y in y_range.start..y_range.end.min(self.height) {
+            // at most 4 lines can be full at once
+            if self.rows[y] == self.full_row && full_rows.len < full_rows.rows.len() {
+                full_rows.rows[full_rows.len] = y;
+                full_rows.len += 1;
+            }
+        }
+        full_rows
+    }
+
+    /// Returns `true` if every row apart from the full rows is empty,
+    /// i.e. removing them will leave nothing on the board.
+    pub fn is_empty_apart_from(&self, full_rows: &FullRows) -> bool {
+        self.rows[..self.height].iter().enumerate().
+            all(|(y, &row)| row == 0 || full_rows.as_slice().contains(&y))
+    }
+
+    /// Removes the full rows found by `full_rows`, moving everything
+    /// above them down.
+    pub fn remove_rows(&mut self, full_rows: &FullRows) {
+        remove_rows(&mut self.rows[..self.height], full_rows, 0);
+    }
+}
+
+/// Removes rows from a layer of the board and moves the rows above them
+/// down, filling the top with empty rows. `full_rows` is in order from
+/// the bottom up.
+fn remove_rows<T: Copy>(layer: &mut [T], full_rows: &FullRows, empty: T) {
+    for (cleared_so_far, &y) in full_rows.as_slice().iter().enumerate() {
+        // adjust the y coordinate for the lines already removed
+        let real_y = y - cleared_so_far;
+        // shift all the grid rows above this line down
+        layer.copy_within(real_y + 1.., real_y);
+        // the upper most grid row doesn't have another row to copy from
+        if let Some(top) = layer.last_mut() {
+            *top = empty;
+        }
+    }
+}
+
+/// The playing board. Its size is picked when it's made.
+/// Collisions and line clears only look at which cells are filled,
+/// the colour of each block is kept alongside for drawing.
 pub struct Board {
-    content: [[TetriminoType; BOARD_WIDTH]; BOARD_HEIGHT],
+    /// Which cells are filled.
+    cells: BitBoard,
+    /// The colour of each filled cell. Anything past the width or
+    /// height of the board is always empty.
+    colors: [[TetriminoType; MAX_BOARD_WIDTH]; MAX_BOARD_HEIGHT],
+    /// The number of rows the player can see.
+    visible_height: usize,
 }
 
 impl Board {
+    #[cfg(test)]
+    /// Makes an empty board of the standard size.
     pub fn new() -> Board {
+        Board::with_size(DEFAULT_BOARD_WIDTH, DEFAULT_VISIBLE_HEIGHT)
+    }
+
+    /// Makes an empty board with the given number of columns and visible rows.
+    /// Both are clamped to the range 4..=MAX_BOARD_WIDTH and 4..=MAX_VISIBLE_HEIGHT.
+    pub fn with_size(width: usize, visible_height: usize) -> Board {
+        let visible_height = visible_height.clamp(MIN_BOARD_SIZE, MAX_VISIBLE_HEIGHT);
         Board {
             // this will make an empty board
-            content: Default::default(),
+            cells: BitBoard::new(width, visible_height + BUFFER_HEIGHT),
+            colors: [[TetriminoType::EmptySpace; MAX_BOARD_WIDTH]; MAX_BOARD_HEIGHT],
+            visible_height,
         }
     }
 
-    /// Returns the `TetriminoType` at the specified xy coordinate.
-    pub fn tetrimino_type_at(&self, x: u8, y: u8) -> TetriminoType {
-        self.content[y as usize][x as usize]
+    /// Returns the number of columns in the board.
+    pub fn width(&self) -> u32 {
+        self.cells.width as u32
+    }
+
+    /// Returns the number of rows in the board, including the buffer zone.
+    pub fn height(&self) -> u32 {
+        self.cells.height as u32
+    }
+
+    /// Returns the number of rows the player can see.
+    pub fn visible_height(&self) -> u32 {
+        self.visible_height as u32
     }
 
-    /// Checks to see if a set of coordinates are within the accepted
-    /// bounds of the playfield. In order to support moving a piece
-    /// on the last row, a y-value of -1 is considered within the
-    /// bounds of the playfield.
-    pub fn is_tetrimino_within_bounds(&self, coords: &[Coord; 4]) -> bool {
-        coords.iter().all(|&c| 0 <= c.x && c.x < BOARD_WIDTH as i32 && 
-                               -1 <= c.y && c.y < BOARD_HEIGHT as i32)
+    /// Returns the `TetriminoType` at the specified xy coordinate.
+    pub fn tetrimino_type_at(&self, x: u8, y: u8) -> TetriminoType {
+        self.colors[y as usize][x as usize]
     }
 
     #[cfg(test)]
     /// A helper function allowing random write access to the board for testing.
     pub fn add_tetrimino_at(&mut self, x: usize, y: usize, tet_type: TetriminoType) {
-        if x < BOARD_WIDTH && y < BOARD_HEIGHT {
-            self.content[y][x] = tet_type;
+        if x < self.cells.width && y < self.cells.height {
+            self.colors[y][x] = tet_type;
+            if tet_type == TetriminoType::EmptySpace {
+                self.cells.rows[y] &= !(1 << x);
+            } else {
+                self.cells.rows[y] |= 1 << x;
+            }
         } else {
             panic!("Invalid x or y coordinate ({},{})", x, y);
         }
@@ -48,50 +262,35 @@ impl Board {
     /// Returns `true` if any of the tetriminos in a piece are already
     /// occupied in the board.
     pub fn is_occupied(&self, coords: &[Coord; 4]) -> bool {
-        // would these coordinates overlap with 
-        // any of the pieces already settled on the board?
-        // NOTE: the cast only holds if the coordinates have already been
-        //       verified to be within the borders of the game board
-        coords.iter().any(|&c| { 
-            if c.y < 0 {
-                false
-            } else {
-                self.content[c.y as usize][c.x as usize] != TetriminoType::EmptySpace
-            }
-        })
+        self.cells.is_occupied(coords)
+    }
+
+    /// Returns `true` if a cell is outside the walls or floor, or
+    /// is already filled. Cells above the board are empty.
+    pub fn is_blocked(&self, c: Coord) -> bool {
+        self.cells.is_blocked(c.x, c.y)
     }
 
-    /// Returns `true` if any of the tetriminos in a piece are at the
-    /// bottom of the board.
-    pub fn is_at_the_bottom(&self, coords: &[Coord; 4]) -> bool {
-        // at least one y coordinate should be equal to -1
-        // and none of the coordinates should be less than -1
-        // -1 is used to allow for movement on the last row
-        coords.iter().any(|&c| c.y == -1) && !coords.iter().any(|&c| c.y < -1)
+    /// Returns `true` if a piece can be put at the coordinates: every cell
+    /// is inside the walls, on or above the floor, below the top of the
+    /// board and empty.
+    pub fn fits(&self, coords: &[Coord; 4]) -> bool {
+        coords.iter().all(|&c| c.y < self.cells.height as i32 && !self.is_blocked(c))
     }
 
     /// Adds a piece to the board at the coordinates.
     /// Returns the range of y-values occupied by the piece.
     /// The y-values constrain the search for lines to remove.
     pub fn add_piece(&mut self, piece: &Piece) -> Range<usize> {
-        let tet_type = 
-            match piece.piece_type {
-                PieceType::I(_) => TetriminoType::I,
-                PieceType::O    => TetriminoType::O,
-                PieceType::J    => TetriminoType::J,
-                PieceType::L    => TetriminoType::L,
-                PieceType::S    => TetriminoType::S,
-                PieceType::Z    => TetriminoType::Z,
-                PieceType::T    => TetriminoType::T,
-            };
+        let tet_type = TetriminoType::from(piece.piece_type);
 
         // add pieces to the board
+        // NOTE: assumption is that these have been verified to be within the board bounds
+        self.cells.add_piece(piece);
         for c in piece.position.iter() {
-            // NOTE: assumption is that these have been verified to be within the board bounds
-            self.content[c.y as usize][c.x as usize] = tet_type;
+            self.colors[c.y as usize][c.x as usize] = tet_type;
         }
 
-
         // determine y coordinate range
         // the y range determines where to check for completed lines
         let mut y_min : i32 =  400;
@@ -109,55 +308,224 @@ impl Board {
         (y_min as usize)..((y_max + 1) as usize)
     }
 
-    /// Updates the board by clearing any full lines within
-    /// the specified y-range.
-    /// Returns the number of lines cleared.
-    pub fn clear_lines(&mut self, y_range: Range<usize>) -> u32 {
-        // this will hold the indices of lines to be removed
-        // at most 4 lines will be removed
-        // -1 will indicate there are no more lines to remove
-        let mut completed_lines : [i32; 4] = [-1, -1, -1, -1];
-        let mut idx = 0;
-        let mut lines_cleared = 0;
-        // check for completed lines and mark all entries with a 2
-        for y in y_range {
-            // save all indices where there isn't a line to clear
-            // NOTE: y must be within the board bounds
-            let is_completed_line = self.content[y as usize].iter().all(|&val| val != TetriminoType::EmptySpace);
-
-            // mark line for deletion if it's a completed line
-            if is_completed_line {
-                completed_lines[idx] = y as i32;
-                idx += 1;
-                lines_cleared += 1;
-            }
+    /// Finds the full lines within the specified y-range,
+    /// from the bottom up.
+    pub fn full_rows(&self, y_range: Range<usize>) -> FullRows {
+        self.cells.full_rows(y_range)
+    }
+
+    /// Returns `true` if every row apart from the full rows is empty,
+    /// i.e. removing them will leave nothing on the board.
+    pub fn is_empty_apart_from(&self, full_rows: &FullRows) -> bool {
+        self.cells.is_empty_apart_from(full_rows)
+    }
+
+    /// Removes the full rows found by `full_rows`, moving everything
+    /// above them down.
+    pub fn remove_rows(&mut self, full_rows: &FullRows) {
+        self.cells.remove_rows(full_rows);
+        let height = self.cells.height;
+        remove_rows(&mut self.colors[..height], full_rows, [TetriminoType::EmptySpace; MAX_BOARD_WIDTH]);
+    }
+}
+
+#[derive(Copy, Clone)]
+/// A read-only look at the stack, for overlays, bots and analytics.
+/// Like the board itself, x counts columns from the left and y counts
+/// rows up from the bottom, so row 0 is the floor. The rows above
+/// `visible_height` are the hidden buffer zone.
+pub struct BoardView<'a> {
+    board: &'a Board,
+}
+
+impl<'a> BoardView<'a> {
+    pub(crate) fn new(board: &'a Board) -> BoardView<'a> {
+        BoardView { board }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.board.cells.width
+    }
+
+    /// Returns the number of rows, including the buffer zone.
+    pub fn height(&self) -> usize {
+        self.board.cells.height
+    }
+
+    /// Returns the number of rows the player can see.
+    pub fn visible_height(&self) -> usize {
+        self.board.visible_height
+    }
+
+    /// Returns `true` if the cell is filled. Cells off the board are empty.
+    pub fn is_filled(&self, x: usize, y: usize) -> bool {
+        self.board.cells.is_filled(x, y)
+    }
+
+    /// Returns a copy of which cells are filled, without the colours.
+    pub fn bits(&self) -> BitBoard {
+        self.board.cells
+    }
+
+    /// Returns the kind of block in a cell. Cells off the board are empty.
+    pub fn cell(&self, x: usize, y: usize) -> TetriminoType {
+        if x < self.width() && y < self.height() {
+            self.board.colors[y][x]
+        } else {
+            TetriminoType::EmptySpace
         }
+    }
 
-        // clear completed lines.
-        for (cleared_so_far, y) in completed_lines.iter().enumerate() {
-            // exit if there are no more lines to clear
-            // (indicated by -1)
-            if *y == -1 {
-                break;
-            }
+    /// Iterates over every cell of the board as `(x, y, block)`,
+    /// row by row from the bottom left.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, TetriminoType)> + 'a {
+        let board = self.board;
+        (0..board.cells.height).flat_map(move |y| {
+            (0..board.cells.width).map(move |x| (x, y, board.colors[y][x]))
+        })
+    }
 
-            // adjust the y coordinate for the lines already removed
-            let real_y = *y - cleared_so_far as i32; 
-            // shift all the grid rows above this line down
-            // the last grid row won't have another row to copy from, so ignore that row until
-            // the end
-            for i in real_y..(BOARD_HEIGHT as i32 - 1) {
-                for x in 0..BOARD_WIDTH {
-                    self.content[i as usize][x] = self.content[i as usize + 1][x];
-                }
-            }
+    /// Returns a row as a bitmask, with bit x set when column x is filled.
+    /// Rows off the board are empty.
+    pub fn row(&self, y: usize) -> u32 {
+        self.board.cells.row(y)
+    }
 
-            // set the upper most grid row to all zeroes, indicating nothing is there
-            for x in self.content[BOARD_HEIGHT-1].iter_mut() {
-                *x = TetriminoType::EmptySpace;
-            }
+    /// Returns the number of filled cells in a row.
+    pub fn row_fill_count(&self, y: usize) -> usize {
+        self.row(y).count_ones() as usize
+    }
+
+    /// Returns the number of filled cells in each row, from the bottom up.
+    pub fn row_fill_counts(&self) -> impl Iterator<Item = usize> + 'a {
+        let view = *self;
+        (0..self.height()).map(move |y| view.row_fill_count(y))
+    }
+
+    /// Returns `true` if every cell of a row is filled.
+    pub fn is_row_full(&self, y: usize) -> bool {
+        y < self.height() && self.row(y) == self.board.cells.full_row
+    }
+
+    /// Returns the height of the stack in a column: one more than the
+    /// highest filled cell, or 0 if the column is empty.
+    pub fn column_height(&self, x: usize) -> usize {
+        if x >= self.width() {
+            return 0;
+        }
+        (0..self.height()).rev().
+            find(|&y| self.board.cells.is_filled_unchecked(x, y)).
+            map_or(0, |y| y + 1)
+    }
+
+    /// Returns the height of each column, from left to right.
+    pub fn column_heights(&self) -> impl Iterator<Item = usize> + 'a {
+        let view = *self;
+        (0..self.width()).map(move |x| view.column_height(x))
+    }
+
+    /// Returns `true` if there are no blocks on the board.
+    pub fn is_empty(&self) -> bool {
+        self.board.cells.is_empty_apart_from(&FullRows::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::{Orientation, PieceType};
+
+    #[test]
+    fn full_rows_are_found_from_the_bits() {
+        let mut board = Board::with_size(4, 20);
+        for x in 0..3 {
+            board.add_tetrimino_at(x, 1, TetriminoType::O);
         }
+        assert!(board.full_rows(0..4).is_empty());
+
+        // an I piece standing up in the last column fills rows 0 to 3
+        let i_piece = Piece {
+            piece_type: PieceType::I,
+            orientation: Orientation::Right,
+            position: [0, 1, 2, 3].map(|y| Coord { x: 3, y }),
+        };
+        let y_range = board.add_piece(&i_piece);
+        assert_eq!(board.full_rows(y_range).as_slice(), &[1]);
+    }
+
+    #[test]
+    fn removing_rows_keeps_the_colours_with_their_blocks() {
+        let mut board = Board::with_size(4, 20);
+        for x in 0..4 {
+            board.add_tetrimino_at(x, 0, TetriminoType::O);
+            board.add_tetrimino_at(x, 2, TetriminoType::I);
+        }
+        board.add_tetrimino_at(1, 1, TetriminoType::T);
+        board.add_tetrimino_at(2, 3, TetriminoType::S);
+
+        let full_rows = board.full_rows(0..4);
+        assert_eq!(full_rows.as_slice(), &[0, 2]);
+        assert!(!board.is_empty_apart_from(&full_rows));
+
+        board.remove_rows(&full_rows);
+        assert_eq!(board.tetrimino_type_at(1, 0), TetriminoType::T);
+        assert_eq!(board.tetrimino_type_at(2, 1), TetriminoType::S);
+        assert!(board.is_occupied(&[Coord { x: 1, y: 0 }, Coord { x: 2, y: 1 }, Coord { x: 0, y: 5 }, Coord { x: 0, y: 6 }]));
+        assert!(!board.is_blocked(Coord { x: 2, y: 0 }));
+        assert!(!board.is_blocked(Coord { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn view_reports_heights_and_fill_counts() {
+        let mut board = Board::with_size(4, 20);
+        board.add_tetrimino_at(0, 0, TetriminoType::O);
+        board.add_tetrimino_at(1, 0, TetriminoType::O);
+        board.add_tetrimino_at(1, 3, TetriminoType::T);
+        let view = BoardView::new(&board);
+
+        assert!(view.column_heights().eq([1, 4, 0, 0]));
+        assert_eq!(view.row_fill_count(0), 2);
+        assert!(view.row_fill_counts().take(4).eq([2, 0, 0, 1]));
+        assert_eq!(view.row(0), 0b11);
+        assert!(view.is_filled(1, 3));
+        assert!(!view.is_filled(1, 2));
+        assert!(!view.is_filled(9, 0));
+        assert_eq!(view.cell(1, 3), TetriminoType::T);
+        assert_eq!(view.cells().filter(|&(_, _, t)| t != TetriminoType::EmptySpace).count(), 3);
+        assert_eq!(view.cells().count(), 4 * view.height());
+        assert!(!view.is_empty());
+        assert!(!view.is_row_full(0));
+    }
+
+    #[test]
+    fn bit_boards_can_be_played_on_without_a_game() {
+        let mut board = Board::with_size(4, 20);
+        board.add_tetrimino_at(0, 0, TetriminoType::O);
+        let mut bits = BoardView::new(&board).bits();
+        assert_eq!(bits.width(), 4);
+        assert_eq!(bits.height(), 40);
+
+        // trying out a placement doesn't touch the game's board
+        for x in 1..4 {
+            assert!(!bits.is_blocked(x, 0));
+            bits.fill(x as usize, 0);
+        }
+        bits.fill(2, 1);
+        assert!(bits.is_blocked(-1, 5));
+        assert!(!bits.is_blocked(0, 40));
+        assert!(!board.is_blocked(Coord { x: 1, y: 0 }));
+
+        let full_rows = bits.full_rows(0..2);
+        assert_eq!(full_rows.as_slice(), &[0]);
+        bits.remove_rows(&full_rows);
+        assert_eq!(bits.row(0), 0b100);
+        assert!(bits.is_filled(2, 0));
+        assert!(!bits.is_filled(2, 1));
+        assert!(!bits.is_filled(9, 0));
 
-        lines_cleared
+        // the size is clamped like the board's
+        assert_eq!(BitBoard::new(100, 1).width(), MAX_BOARD_WIDTH);
+        assert_eq!(BitBoard::new(100, 1).height(), 4);
     }
 }
@@ -1,9 +1,18 @@
-use crate::board::Board;
-use crate::pieces::{Piece, PieceType, PIECE_TYPES};
+use crate::board::{Board, FullRows};
+pub use crate::board::BoardView;
+use crate::pieces::{Orientation, Piece, PieceType};
 use crate::coord::Coord;
 use crate::game_renderer::TetriminoType;
 use crate::game_renderer::GameRenderer;
-use crate::rng::Rng;
+use crate::randomizer::Randomizer;
+use crate::gravity::{GravityCurve, CELL, TWENTY_G};
+use crate::input::{InputEvent, InputTracker};
+use crate::scoring::{Classic, LineClear, ScoringRules};
+pub use crate::scoring::TSpin;
+pub use crate::input::Input;
+pub use crate::board::{DEFAULT_BOARD_WIDTH, DEFAULT_VISIBLE_HEIGHT, MAX_BOARD_WIDTH, MAX_VISIBLE_HEIGHT};
+
+use core::time::Duration;
 
 #[derive(Default)]
 /// A structure used to hold rendering information
@@ -11,132 +20,503 @@ use crate::rng::Rng;
 /// `partial_draw` mode.
 struct RenderInfo {
     previous_piece_pos: Option<[Coord; 4]>,
+    previous_ghost_pos: Option<[Coord; 4]>,
     newly_settled_pieces: Option<[Coord; 4]>,
-    lines_cleared: bool,
+    /// Set when lines are cleared, or when the changes from several
+    /// frames can't be drawn one piece at a time.
+    redraw_board: bool,
     new_score: Option<u32>,
     new_level: Option<usize>,
+    hold_changed: bool,
+    next_changed: bool,
+    line_clear_changed: bool,
 }
 
-#[derive(Default)]
-/// Represents the different types of user input possible.
-pub struct Input {
-    /// true when user attempts to move the piece left
-    pub left: bool,
-    /// true when user attempts to move the piece right
-    pub right: bool,
-    /// true when user attempts to move the piece down
-    pub down: bool,
-    /// true when user wishes to rotate a piece clockwise
-    pub cw_rotate: bool,
-    /// true when user wishes to rotate a piece counterclockwise
-    pub ccw_rotate: bool,
+impl RenderInfo {
+    /// Combines the changes from two frames that ran without a draw in between.
+    fn merge(self, later: RenderInfo) -> RenderInfo {
+        let moved_blocks = |info: &RenderInfo| {
+            info.previous_piece_pos.is_some() ||
+            info.previous_ghost_pos.is_some() ||
+            info.newly_settled_pieces.is_some()
+        };
+        // only one set of positions can be erased and redrawn,
+        // if both frames moved blocks around redraw everything
+        let both_moved_blocks = moved_blocks(&self) && moved_blocks(&later);
+
+        RenderInfo {
+            previous_piece_pos: self.previous_piece_pos.or(later.previous_piece_pos),
+            previous_ghost_pos: self.previous_ghost_pos.or(later.previous_ghost_pos),
+            newly_settled_pieces: self.newly_settled_pieces.or(later.newly_settled_pieces),
+            redraw_board: self.redraw_board || later.redraw_board || both_moved_blocks,
+            new_score: later.new_score.or(self.new_score),
+            new_level: later.new_level.or(self.new_level),
+            hold_changed: self.hold_changed || later.hold_changed,
+            next_changed: self.next_changed || later.next_changed,
+            line_clear_changed: self.line_clear_changed || later.line_clear_changed,
+        }
+    }
+}
+
+/// Controls how a held left or right input repeats.
+/// All values are in frames.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Handling {
+    /// Delayed Auto Shift: how long a direction has to be held
+    /// before the piece starts moving on its own.
+    pub das: u32,
+    /// Auto Repeat Rate: how long to wait between moves once DAS has
+    /// charged. An ARR of 0 moves the piece all the way to the wall.
+    pub arr: u32,
+}
+
+impl Default for Handling {
+    fn default() -> Self {
+        Handling {
+            das: 10,
+            arr: 2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The direction a piece is being shifted in.
+enum Shift {
+    Left,
+    Right,
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// Running totals for the current game.
+pub struct Statistics {
+    /// The number of pieces locked onto the board.
+    pub pieces: u32,
+    /// The number of lines cleared.
+    pub lines: u32,
+    /// The number of times the board has been cleared completely.
+    pub perfect_clears: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The last thing that successfully moved the current piece.
+/// A T piece only counts as spun if its last move was a rotation.
+pub enum MoveKind {
+    /// The piece hasn't moved since it spawned.
+    Spawn,
+    /// Moved left or right.
+    Shift,
+    /// Fell, was soft dropped or was hard dropped.
+    Fall,
+    /// `kick` is the index of the wall kick that was used, 0 for none.
+    Rotation { kick: usize },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// What the game is doing this frame.
+pub enum Phase {
+    /// The current piece is falling.
+    Falling,
+    /// The current piece is resting on the stack, waiting for the lock delay.
+    Locking,
+    /// The piece has locked and filled some rows. The rows stay on the
+    /// board until the line clear delay is over, so the renderer can
+    /// animate them before the stack collapses.
+    LineClear,
+    /// Waiting for the next piece to appear (ARE).
+    Spawning,
+}
+
+/// The wall kick that lets a T piece squeeze into a T-spin triple slot.
+/// A rotation using this kick always counts as a full T-spin.
+const TST_KICK : usize = 4;
+
+/// The number of times moving or rotating a piece on the ground
+/// can restart the lock delay.
+const MAX_LOCK_RESETS : u32 = 15;
+
+/// The number of frames the game runs each second. All the timings,
+/// including gravity, DAS and lock delay, are given in these frames.
+pub const FRAMES_PER_SECOND : u64 = 60;
+
+const NANOS_PER_SECOND : u64 = 1_000_000_000;
+
+/// The most frames `update` will run in one call. Any time beyond that is
+/// dropped, so a game that was stalled doesn't fast forward to catch up.
+const MAX_FRAMES_PER_UPDATE : u32 = 60;
+
+/// The longest preview of upcoming pieces a game can show.
+pub const MAX_PREVIEW_LENGTH : usize = 6;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Settings that are fixed for the lifetime of a game.
+pub struct GameConfig {
+    /// The number of upcoming pieces shown to the player.
+    /// Clamped to the range 1..=MAX_PREVIEW_LENGTH.
+    pub preview_length: usize,
+    /// The number of frames a piece may rest on the ground before it locks.
+    pub lock_delay: u32,
+    /// How held left and right inputs repeat.
+    pub handling: Handling,
+    /// How fast pieces fall at each level.
+    pub gravity: GravityCurve,
+    /// Entry delay (ARE): the number of frames between a piece locking
+    /// and the next one appearing. 0 spawns the next piece straight away.
+    pub are: u32,
+    /// The number of frames full rows stay on the board before they are
+    /// removed, on top of the entry delay. 0 removes them straight away.
+    pub line_clear_delay: u32,
+    /// The number of columns on the board.
+    /// Clamped to the range 4..=MAX_BOARD_WIDTH.
+    pub board_width: usize,
+    /// The number of rows of the board the player can see. There's a hidden
+    /// buffer zone above them that pieces spawn into.
+    /// Clamped to the range 4..=MAX_VISIBLE_HEIGHT.
+    pub visible_height: usize,
 }
 
-/// Used to provide a wait between certain user inputs.
-/// This is used for rotations and translations.
-const COOLDOWN : u32 = 10;
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            preview_length: 5,
+            lock_delay: 30,
+            handling: Default::default(),
+            gravity: Default::default(),
+            are: 0,
+            line_clear_delay: 0,
+            board_width: DEFAULT_BOARD_WIDTH,
+            visible_height: DEFAULT_VISIBLE_HEIGHT,
+        }
+    }
+}
 
-pub struct Game {
-    /// Holds all possible pieces and their spawn locations.
-    /// Gets shuffled after all pieces have been used.
-    pieces: [Piece; 7],
+pub struct Game<S: ScoringRules = Classic> {
+    /// The upcoming pieces, in the order they will be played. This is
+    /// kept as long as the longest preview and topped up from the
+    /// randomizer every time a piece is taken off the front.
+    next_queue: [PieceType; MAX_PREVIEW_LENGTH],
     /// The currently falling piece
     current_piece: Piece,
+    /// The piece set aside by the player, at its spawn location.
+    held_piece: Option<Piece>,
+    /// Whether the player may still use hold before the current piece locks.
+    can_hold: bool,
     /// The playing board
     board: Board,
-    /// Index for the pieces array.
-    piece_index: usize,
+    /// The number of upcoming pieces shown to the player.
+    preview_length: usize,
     /// Indicates whether the game is still active.
     state: GameState,
-    /// Represents the current displacement of the active piece.
-    displacement: f32,
+    /// What the game is doing this frame. This is never `Locking`,
+    /// whether the piece is on the ground is worked out when asked.
+    phase: Phase,
+    /// The number of frames left in the line clear or entry delay.
+    phase_timer: u32,
+    /// The rows filled by the last piece, waiting to be removed.
+    full_rows: FullRows,
+    /// The number of frames between a piece locking and the next one appearing.
+    are: u32,
+    /// The number of frames full rows stay on the board before they are removed.
+    line_clear_delay: u32,
+    /// Represents the current displacement of the active piece,
+    /// in 1/65536 of a cell.
+    displacement: u32,
     /// The current level. This determines how fast the pieces fall.
     level: usize,
-    /// The current score, used to determine which level has been reached.
+    /// How fast pieces fall at each level.
+    gravity: GravityCurve,
+    /// How fast pieces fall at the current level, in 1/65536 of a cell per frame.
+    fall_speed: u32,
+    /// The current score.
     score: u32,
-    /// The next score to make to get to the next level.
-    next_level_score: u32,
-    /// Counter to keep track of when to allow another rotation.
-    rotation_cooldown_counter: u32,
-    /// Counter to keep track of when to allow another translation.
-    translation_cooldown_counter: u32,
+    /// Decides the points for each action and when to level up.
+    rules: S,
+    /// The number of line clears in a row, minus one.
+    /// `None` when the last piece didn't clear any lines.
+    combo: Option<u32>,
+    /// Whether the last line clear was a difficult one.
+    back_to_back: bool,
+    /// The last line clear or T-spin, for the renderer to announce.
+    last_line_clear: Option<LineClear>,
+    /// The last thing that moved the current piece.
+    last_movement: MoveKind,
+    /// Whether the last piece to lock was spun into place.
+    last_t_spin: TSpin,
+    /// Running totals for the current game.
+    statistics: Statistics,
+    /// The number of frames a piece may rest on the ground before it locks.
+    lock_delay: u32,
+    /// The number of frames the current piece has spent on the ground.
+    lock_timer: u32,
+    /// The number of times the lock delay has been restarted by the player.
+    lock_resets: u32,
+    /// The lowest row the current piece has reached.
+    lowest_row: i32,
+    /// How held left and right inputs repeat.
+    handling: Handling,
+    /// The direction that was held last frame.
+    shift_direction: Option<Shift>,
+    /// The number of frames the current direction has been held, up to the DAS.
+    das_counter: u32,
+    /// The number of frames since the last auto repeated move.
+    arr_counter: u32,
+    /// The state of the controls, used to tell when a button has just been pressed.
+    controls: InputTracker,
+    /// Time passed to `update` that hasn't been run yet, in nanoseconds
+    /// times the frame rate, so a frame is exactly one second's worth.
+    unsimulated_time: u64,
     /// Rendering info
     render_info: RenderInfo,
+    /// Set when everything needs redrawing, until a frame's render info includes it.
+    redraw_pending: bool,
 }
 
-/// 1 unit of gravity = moving one cell
-/// these are the gravity constants for 60 fps
-/// source: https://harddrop.com/wiki/Tetris_Worlds
-const GRAVITY : [f32; 15] = [
-    0.01667,
-    0.021017,
-    0.026977,
-    0.035356,
-    0.04693,
-    0.06361,
-    0.0879,
-    0.1236,
-    0.1775,
-    0.2598,
-    0.388,
-    0.59,
-    0.92,
-    1.46,
-    2.36,
-];
-
 #[derive(Copy, Clone, Debug, PartialEq)]
 /// Represents whether the game is over or in play.
 pub enum GameState {
     Playing,
-    GameOver,
+    /// Nothing moves until the game is resumed.
+    Paused,
+    GameOver(TopOut),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The reason the stack got too high to keep playing.
+pub enum TopOut {
+    /// A new piece appeared overlapping the stack.
+    BlockOut,
+    /// A piece locked entirely in the buffer zone above the visible field.
+    LockOut,
 }
 
 impl Game {
     /// Creates a new game "instance."
-    pub fn new<R: Rng>(rng: &mut R) -> Self {
-        let mut tets = PIECE_TYPES;
-        // shuffle the pieces randomly
-        // do a knuth shuffle to permuate the pieces
-        for i in 0..tets.len() {
-            let index = rng.next();
-            if index != i {
-                tets.swap(i, index);
-            }
-        }
+    pub fn new<R: Randomizer>(randomizer: &mut R) -> Self {
+        Game::with_config(randomizer, GameConfig::default())
+    }
+
+    /// Creates a new game "instance" using the provided settings.
+    pub fn with_config<R: Randomizer>(randomizer: &mut R, config: GameConfig) -> Self {
+        Game::with_rules(randomizer, config, Classic::default())
+    }
 
+    #[cfg(test)]
+    /// A helper constructor for testing.
+    fn new_test() -> Game {
         Game {
-            pieces: tets,
-            current_piece: tets[0],
+            next_queue: [PieceType::O, PieceType::J, PieceType::L,
+                         PieceType::S, PieceType::Z, PieceType::T],
+            current_piece: Piece::spawn(PieceType::I),
+            held_piece: None,
+            can_hold: true,
             board: Board::new(),
-            piece_index: 0,
+            preview_length: MAX_PREVIEW_LENGTH,
             state: GameState::Playing,
-            displacement: 0.0,
+            phase: Phase::Falling,
+            phase_timer: 0,
+            full_rows: Default::default(),
+            are: GameConfig::default().are,
+            line_clear_delay: GameConfig::default().line_clear_delay,
+            displacement: 0,
             level: 1,
+            gravity: Default::default(),
+            fall_speed: GravityCurve::Classic.gravity(1),
             score: 0,
-            next_level_score: 5,
-            rotation_cooldown_counter: 0,
-            translation_cooldown_counter: 0,
+            rules: Classic::default(),
+            combo: None,
+            back_to_back: false,
+            last_line_clear: None,
+            last_movement: MoveKind::Spawn,
+            last_t_spin: TSpin::None,
+            statistics: Default::default(),
+            lock_delay: GameConfig::default().lock_delay,
+            lock_timer: 0,
+            lock_resets: 0,
+            lowest_row: Self::lowest_row(&Piece::spawn(PieceType::I)),
+            handling: Default::default(),
+            shift_direction: None,
+            das_counter: 0,
+            arr_counter: 0,
+            controls: InputTracker::new(),
+            unsimulated_time: 0,
             render_info: Default::default(),
+            redraw_pending: false,
         }
     }
+}
 
-    #[cfg(test)]
-    /// A helper constructor for testing.
-    fn new_test() -> Game {
-        Game {
-            pieces: PIECE_TYPES,
-            current_piece: PIECE_TYPES[0],
-            board: Board::new(),
-            piece_index: 0,
+impl<S: ScoringRules> Game<S> {
+    /// Creates a new game "instance" that scores and levels up by the provided rules.
+    pub fn with_rules<R: Randomizer>(randomizer: &mut R, config: GameConfig, rules: S) -> Self {
+        let board = Board::with_size(config.board_width, config.visible_height);
+        let current_piece = Piece::spawn_on(randomizer.next_piece(),
+                                            board.width() as usize,
+                                            board.visible_height() as usize);
+        let mut next_queue = [PieceType::I; MAX_PREVIEW_LENGTH];
+        for piece_type in next_queue.iter_mut() {
+            *piece_type = randomizer.next_piece();
+        }
+
+        let mut game = Game {
+            next_queue,
+            current_piece,
+            held_piece: None,
+            can_hold: true,
+            board,
+            preview_length: config.preview_length.clamp(1, MAX_PREVIEW_LENGTH),
             state: GameState::Playing,
-            displacement: 0.0,
-            level: 1,
+            phase: Phase::Falling,
+            phase_timer: 0,
+            full_rows: Default::default(),
+            are: config.are,
+            line_clear_delay: config.line_clear_delay,
+            displacement: 0,
+            level: rules.start_level(),
+            gravity: config.gravity,
+            fall_speed: config.gravity.gravity(rules.start_level()),
             score: 0,
-            next_level_score: 5,
-            rotation_cooldown_counter: 0,
-            translation_cooldown_counter: 0,
+            rules,
+            combo: None,
+            back_to_back: false,
+            last_line_clear: None,
+            last_movement: MoveKind::Spawn,
+            last_t_spin: TSpin::None,
+            statistics: Default::default(),
+            lock_delay: config.lock_delay,
+            lock_timer: 0,
+            lock_resets: 0,
+            lowest_row: Self::lowest_row(&current_piece),
+            handling: config.handling,
+            shift_direction: None,
+            das_counter: 0,
+            arr_counter: 0,
+            controls: InputTracker::new(),
+            unsimulated_time: 0,
             render_info: Default::default(),
+            redraw_pending: false,
+        };
+        game.spawn_piece(current_piece);
+        game
+    }
+
+    /// Starts a new game with the same settings and fresh rules, reusing this
+    /// instance. Pass a randomizer with a new seed to play different pieces.
+    pub fn reset<R: Randomizer>(&mut self, randomizer: &mut R) where S: Default {
+        *self = Self::with_rules(randomizer, self.config(), S::default());
+        self.redraw_everything();
+    }
+
+    /// Pauses a game in play. Frames still run, so time spent paused
+    /// doesn't pile up, but nothing moves until the game is resumed.
+    pub fn pause(&mut self) {
+        if self.state == GameState::Playing {
+            self.state = GameState::Paused;
+        }
+    }
+
+    /// Carries on with a paused game.
+    pub fn resume(&mut self) {
+        if self.state == GameState::Paused {
+            self.state = GameState::Playing;
+            // the renderer may have hidden the playfield while the game was paused
+            self.redraw_everything();
+        }
+    }
+
+    /// Reports whether the game is in play, paused or over.
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Reports the settings the game is being played with.
+    pub fn config(&self) -> GameConfig {
+        GameConfig {
+            preview_length: self.preview_length,
+            lock_delay: self.lock_delay,
+            handling: self.handling,
+            gravity: self.gravity,
+            are: self.are,
+            line_clear_delay: self.line_clear_delay,
+            board_width: self.board.width() as usize,
+            visible_height: self.board.visible_height() as usize,
+        }
+    }
+
+    /// Makes the next draw redraw everything, not just what changed.
+    /// This lasts until the render info of a frame or update includes it.
+    fn redraw_everything(&mut self) {
+        self.redraw_pending = true;
+        let full = self.full_render_info();
+        self.render_info = core::mem::take(&mut self.render_info).merge(full);
+    }
+
+    /// Returns render info that redraws everything.
+    fn full_render_info(&self) -> RenderInfo {
+        RenderInfo {
+            redraw_board: true,
+            new_score: Some(self.score),
+            new_level: Some(self.level),
+            hold_changed: true,
+            next_changed: true,
+            line_clear_changed: true,
+            .. Default::default()
+        }
+    }
+
+    /// Returns the render info to start a frame with: nothing has changed
+    /// yet, unless a full redraw is still waiting to be drawn.
+    fn take_pending_render_info(&mut self) -> RenderInfo {
+        if core::mem::take(&mut self.redraw_pending) {
+            self.full_render_info()
+        } else {
+            Default::default()
+        }
+    }
+
+    /// Works out how many cells the piece should be shifted this frame
+    /// using Delayed Auto Shift and Auto Repeat Rate.
+    /// The DAS keeps charging across pieces, so a direction held while a
+    /// piece locks carries over to the next one.
+    fn auto_shift(&mut self, input: &Input) -> u32 {
+        let direction =
+            if input.left && !input.right {
+                Some(Shift::Left)
+            } else if input.right && !input.left {
+                Some(Shift::Right)
+            } else {
+                None
+            };
+
+        if direction != self.shift_direction {
+            // pressing a new direction always moves the piece straight away
+            self.shift_direction = direction;
+            self.das_counter = 0;
+            self.arr_counter = 0;
+            return if direction.is_some() { 1 } else { 0 };
+        }
+
+        if direction.is_none() {
+            return 0;
+        }
+
+        if self.das_counter < self.handling.das {
+            self.das_counter += 1;
+            if self.das_counter < self.handling.das {
+                return 0;
+            }
+            // DAS has just charged, the first repeat happens straight away
+            self.arr_counter = self.handling.arr;
+        } else {
+            self.arr_counter += 1;
+        }
+
+        if self.handling.arr == 0 {
+            // move as far as the piece will go
+            self.board.width()
+        } else if self.arr_counter >= self.handling.arr {
+            self.arr_counter = 0;
+            1
+        } else {
+            0
         }
     }
 
@@ -159,92 +539,202 @@ impl Game {
         translated_piece.filter(accept_new_position) 
     }
 
-    /// Processes input for rotation input. 
+    /// Processes input for rotation input.
+    /// Returns the rotated piece along with the index of the kick that was used.
     fn handle_rotation_input<P>(input: &Input, piece: &Piece, accept_new_position: P)
-        -> Option<Piece> where 
+        -> Option<(Piece, usize)> where
         P : Fn(&Piece) -> bool {
-        let rotated_piece = 
+        let rotation_candidates =
             if input.cw_rotate && !input.ccw_rotate {
-                Some(piece.cw_rot())
+                Some(piece.cw_rot_kicks())
             } else if input.ccw_rotate && !input.cw_rotate {
-                Some(piece.ccw_rot())
+                Some(piece.ccw_rot_kicks())
             } else {
                 None
             };
-        // if the rotated piece is within the playfield
-        // and it doesn't collide with any of the pieces on the board
-        // accept the rotation
-        rotated_piece.filter(accept_new_position)
+        // try each of the SRS kicks in order, accepting the first one
+        // that is within the playfield and doesn't collide with any of
+        // the pieces on the board
+        rotation_candidates.and_then(|candidates| {
+            candidates.into_iter().zip(0..).find(|(p, _)| accept_new_position(p))
+        })
     }
 
-    // TODO: try to make less ugly
     /// Processes vertical movement - checks for collisions and pieces hitting
-    /// the bottom of the playfield.
+    /// the bottom of the playfield. The piece stops as soon as the next row
+    /// down would leave the board or overlap the stack.
     fn handle_vertical_movement(piece: &Piece, board: &Board, displacement: u32)
         -> (Piece, bool) {
         let mut relocated_piece = *piece;
-        let mut is_settled = false;
         for _ in 1..=displacement {
-            let previous_piece = relocated_piece;
-            relocated_piece = relocated_piece.apply_gravity(1);
-
-            if board.is_tetrimino_within_bounds(&relocated_piece.position) {
-                let collision = board.is_occupied(&relocated_piece.position);
-                let at_bottom = board.is_at_the_bottom(&relocated_piece.position);
-
-                relocated_piece  =
-                    if collision || at_bottom {
-                        // if there's been a collision or the piece is at the bottom (really one past the bottom), the piece should stay at its previous location
-                        previous_piece
-                    }  else {
-                        // otherwise, the translation is valid
-                        relocated_piece
-                    };
-
-                is_settled = collision || at_bottom;
-                if is_settled {
-                    break;
-                }
+            let moved_piece = relocated_piece.apply_gravity(1);
+            if !board.fits(&moved_piece.position) {
+                // the piece has settled where it was
+                return (relocated_piece, true);
             }
+            relocated_piece = moved_piece;
         }
 
-        (relocated_piece, is_settled)
+        (relocated_piece, false)
     }
 
     /// The main loop for the game.
-    pub fn run_loop<R: Rng>(&mut self, input: &Input, rng: &mut R) -> GameState {
-        if self.state == GameState::GameOver {
-            return self.state
+    /// `input` is a snapshot of which buttons are currently down.
+    pub fn run_loop<R: Randomizer>(&mut self, input: &Input, randomizer: &mut R) -> GameState {
+        self.controls.set(input);
+        self.step(randomizer)
+    }
+
+    /// Queues a button press or release for the next call to `step`.
+    /// This is an alternative to passing a snapshot to `run_loop`.
+    pub fn push_input_event(&mut self, event: InputEvent) {
+        self.controls.push_event(event);
+    }
+
+    /// Reports which buttons are pressed, held or released this frame.
+    pub fn controls(&self) -> &InputTracker {
+        &self.controls
+    }
+
+    /// Updates the state of the controls from a snapshot of which
+    /// buttons are down, ready for the next call to `update` or `step`.
+    pub fn set_input(&mut self, input: &Input) {
+        self.controls.set(input);
+    }
+
+    /// Advances the game by the time that has passed since the last update,
+    /// using the input events queued with `push_input_event`. The game still
+    /// runs in fixed frames of 1/60th of a second, as many as fit into the
+    /// time that has passed. The rest is saved for the next update.
+    /// This lets the game keep the right speed whatever the display's refresh rate.
+    pub fn update<R: Randomizer>(&mut self, dt: Duration, randomizer: &mut R) -> GameState {
+        let dt = u64::try_from(dt.as_nanos()).unwrap_or(u64::MAX);
+        self.unsimulated_time = self.unsimulated_time.saturating_add(dt.saturating_mul(FRAMES_PER_SECOND));
+
+        let mut render_info: Option<RenderInfo> = None;
+        let mut frames = 0;
+        while self.unsimulated_time >= NANOS_PER_SECOND {
+            if frames == MAX_FRAMES_PER_UPDATE {
+                self.unsimulated_time = 0;
+                break;
+            }
+            self.unsimulated_time -= NANOS_PER_SECOND;
+            frames += 1;
+
+            let _ = self.step(randomizer);
+            let frame_info = core::mem::take(&mut self.render_info);
+            render_info = Some(match render_info {
+                Some(earlier) => earlier.merge(frame_info),
+                None => frame_info,
+            });
         }
 
-        let valid_piece_location = |p: &Piece| { 
-            self.board.is_tetrimino_within_bounds(&p.position) &&
-            !self.board.is_occupied(&p.position)
+        // keep the changes from every frame that ran for the next draw
+        self.render_info = match render_info {
+            Some(render_info) => render_info,
+            None => self.take_pending_render_info(),
         };
+        self.state
+    }
+
+    /// Advances the game by one frame using the input events queued
+    /// with `push_input_event`.
+    pub fn step<R: Randomizer>(&mut self, randomizer: &mut R) -> GameState {
+        self.controls.apply_events();
+        let state = self.run_frame(randomizer);
+        self.controls.end_frame();
+        state
+    }
+
+    /// Runs one frame of the game with the current state of the controls.
+    fn run_frame<R: Randomizer>(&mut self, randomizer: &mut R) -> GameState {
+        if self.state != GameState::Playing {
+            return self.state
+        }
+
+        // buttons that have just been pressed, for actions that
+        // shouldn't repeat while the button is held
+        let pressed = self.controls.pressed_input();
+        // buttons that are down, or were tapped since the last frame
+        let input = &self.controls.active_input();
 
         // reset render info
-        self.render_info = Default::default();
+        self.render_info = self.take_pending_render_info();
+
+        // ------------
+        //    DELAYS
+        // ------------
+        if self.phase != Phase::Falling {
+            // there's no piece to move, but keep charging the DAS so
+            // a direction held through the delay shifts the next piece
+            let _ = self.auto_shift(input);
+
+            self.phase_timer = self.phase_timer.saturating_sub(1);
+            if self.phase_timer == 0 {
+                if self.phase == Phase::LineClear {
+                    self.remove_full_rows();
+                    self.start_entry_delay(randomizer);
+                } else {
+                    self.spawn_next_piece(randomizer);
+                }
+            }
+
+            return self.state;
+        }
 
         // save a copy of the piece's current position
         let previous_piece = self.current_piece;
+        let previous_ghost = self.ghost_piece();
+        let previous_score = self.score;
+
+        // ----------
+        //    HOLD
+        // ----------
+        if pressed.hold && self.can_hold {
+            let piece_to_hold = self.current_piece.respawned(self.board.width() as usize,
+                                                             self.board.visible_height() as usize);
+            let piece_to_play =
+                match self.held_piece {
+                    Some(held_piece) => held_piece,
+                    None => self.next_piece(randomizer),
+                };
+            self.held_piece = Some(piece_to_hold);
+            // the piece can only be swapped back once the current one has locked
+            self.can_hold = false;
+            self.displacement = 0;
+            self.spawn_piece(piece_to_play);
+            self.render_info.hold_changed = true;
+
+            if self.state != GameState::Playing {
+                return self.state;
+            }
+        }
+
+        let shifts = self.auto_shift(input);
+
+        let valid_piece_location = |p: &Piece| self.board.fits(&p.position);
+
+        // whether the player successfully moved or rotated the piece this frame
+        let mut moved_by_player = false;
 
         // -------------------------
         //    HORIZONTAL MOVEMENT
         // -------------------------
-        if self.translation_cooldown_counter > 0 {
-            self.translation_cooldown_counter -= 1;
-        } else { // counter is u32, so hitting this branch means it's equal to zero
-            let translated_piece = Game::handle_horizontal_input(
+        for _ in 0..shifts {
+            let translated_piece = Self::handle_horizontal_input(
                                                input,
                                                &self.current_piece,
                                                valid_piece_location);
 
-            if let Some(candidate) = translated_piece {
-                // update the current piece information
-                self.current_piece = candidate;
-                // only apply the translation cooldown if the piece
-                // has successfully been moved
-                self.translation_cooldown_counter = COOLDOWN;
+            match translated_piece {
+                Some(candidate) => {
+                    // update the current piece information
+                    self.current_piece = candidate;
+                    self.last_movement = MoveKind::Shift;
+                    moved_by_player = true;
+                },
+                // the piece has hit a wall or the stack
+                None => break,
             }
         }
 
@@ -252,104 +742,94 @@ impl Game {
         // --------------------
         //    PIECE ROTATION
         // --------------------
-        if self.rotation_cooldown_counter > 0 {
-            self.rotation_cooldown_counter -= 1;
-        } else { // counter is u32, so hitting this branch means it's equal to zero
-            let rotated_piece = Game::handle_rotation_input(
-                                            input,
-                                            &self.current_piece,
-                                            valid_piece_location);
-
-            if let Some(candidate) = rotated_piece {
-                // update the current piece information
-                self.current_piece = candidate;
-                // only apply the rotation cooldown if the piece
-                // has successfully been rotated
-                self.rotation_cooldown_counter = COOLDOWN;
-            }
+        // only rotate when a rotation button has just been pressed,
+        // holding the button down shouldn't spin the piece
+        let rotated_piece = Self::handle_rotation_input(
+                                        &pressed,
+                                        &self.current_piece,
+                                        valid_piece_location);
+
+        if let Some((candidate, kick)) = rotated_piece {
+            // update the current piece information
+            self.current_piece = candidate;
+            self.last_movement = MoveKind::Rotation { kick };
+            moved_by_player = true;
         }
 
         // -----------------------
         //    VERTICAL MOVEMENT
         // -----------------------
-        self.displacement += GRAVITY[self.level-1];
-
-        if (self.displacement as u32) > 0 || input.down {
-
-            // choose the displacement value we will apply
-            let displacement =
-                if input.down {
-                    // move the piece down at least 1 cell per frame while the user is holding the
-                    // down button
-                    core::cmp::max(1, self.displacement as u32 + 1)
-                } else {
-                    self.displacement as u32
-                };
-
-            // reset internal displacement
-            self.displacement = if input.down { 0.0 } else { self.displacement - displacement as f32 };
-
-
-            let (updated_piece, is_settled) = Game::handle_vertical_movement(
-                                                          &self.current_piece,
-                                                          &self.board,
-                                                          displacement);
+        if pressed.hard_drop {
+            // drop the piece as far as it will go and lock it right away
+            let (dropped_piece, _) = Self::handle_vertical_movement(
+                                             &self.current_piece,
+                                             &self.board,
+                                             self.board.height());
+
+            let rows_dropped = self.current_piece.position[0].y - dropped_piece.position[0].y;
+            self.score += self.rules.hard_drop_points(rows_dropped as u32);
+            self.displacement = 0;
+            if rows_dropped > 0 {
+                self.last_movement = MoveKind::Fall;
+            }
 
-            if is_settled {
-                // add the piece to the board
-                let y_range = self.board.add_piece(&updated_piece);
+            self.lock_piece(&dropped_piece, randomizer);
+        } else {
+            self.displacement += self.fall_speed;
 
-                // determine how many lines were cleared after adding this piece
-                let lines_cleared = self.board.clear_lines(y_range);
+            if self.displacement >= CELL || input.down {
 
-                // update the score based on the number of lines cleared
-                self.score +=
-                    if lines_cleared == 1 {
-                        1
-                    } else if lines_cleared == 2 {
-                        3
-                    } else if lines_cleared == 3 {
-                        5
-                    } else if lines_cleared == 4 {
-                        8
+                // choose the displacement value we will apply
+                let displacement =
+                    if input.down {
+                        // move the piece down at least 1 cell per frame while the user is holding the
+                        // down button
+                        core::cmp::max(1, self.displacement / CELL + 1)
                     } else {
-                        0
+                        self.displacement / CELL
                     };
 
-                let off_to_a_new_level = self.score > self.next_level_score && self.level < 15;
-                if off_to_a_new_level {
-                    self.level += 1;
-                    self.next_level_score += 5 * (self.level + 1) as u32;
-                }
-                // save render info
-                // TODO: can we make the render info only get compiled if performing a
-                //       parial redraw?
-                let lines_were_cleared = lines_cleared > 0; // intermediate variable to shorten line length
-                self.render_info.lines_cleared = lines_were_cleared;
-                self.render_info.new_score = if lines_were_cleared { Some(self.score) } else { None };
-                self.render_info.new_level = if off_to_a_new_level { Some(self.level) } else { None };
-                // save the position of these pieces for the next render cycle
-                self.render_info.newly_settled_pieces = Some(updated_piece.position);
-
-                // move to the next piece
-                self.piece_index += 1;
-                // if all of the pieces have been used, shuffle the pieces
-                if self.piece_index == self.pieces.len() {
-                    // do a knuth shuffle to create a permutation of the pieces
-                    for i in 0..self.pieces.len() {
-                        let index = rng.next();
-                        if index != i {
-                            self.pieces.swap(i, index);
-                        }
+                // reset internal displacement
+                self.displacement = if input.down { 0 } else { self.displacement - displacement * CELL };
+
+                let (updated_piece, _) = Self::handle_vertical_movement(&self.current_piece, &self.board, displacement);
+                let rows_fallen = Self::lowest_row(&self.current_piece) - Self::lowest_row(&updated_piece);
+                if rows_fallen > 0 {
+                    self.last_movement = MoveKind::Fall;
+                    if input.down {
+                        self.score += self.rules.soft_drop_points(rows_fallen as u32);
                     }
-                    // reset the index
-                    self.piece_index = 0;
                 }
-                // set new current piece
-                self.current_piece = self.pieces[self.piece_index];
-            } else {
                 self.current_piece = updated_piece;
             }
+
+            // ----------------
+            //    LOCK DELAY
+            // ----------------
+            let lowest_row = Self::lowest_row(&self.current_piece);
+            if lowest_row < self.lowest_row {
+                // step reset: falling further than before gives the player a fresh lock delay
+                self.lowest_row = lowest_row;
+                self.lock_timer = 0;
+                self.lock_resets = 0;
+            } else if moved_by_player && self.lock_timer > 0 && self.lock_resets < MAX_LOCK_RESETS {
+                // move reset: moving or rotating a piece on the ground restarts the lock delay,
+                // but only so many times, otherwise a piece could be kept in play forever
+                self.lock_timer = 0;
+                self.lock_resets += 1;
+            }
+
+            if Self::is_grounded(&self.current_piece, &self.board) {
+                self.lock_timer += 1;
+                if self.lock_timer >= self.lock_delay {
+                    let piece = self.current_piece;
+                    self.lock_piece(&piece, randomizer);
+                }
+            }
+        }
+
+        if self.score != previous_score {
+            self.render_info.new_score = Some(self.score);
         }
 
         let piece_has_moved = self.current_piece.position.iter().
@@ -361,146 +841,498 @@ impl Game {
             } else {
                 None
             };
+        self.render_info.previous_ghost_pos =
+            if self.ghost_piece().position != previous_ghost.position {
+                Some(previous_ghost.position)
+            } else {
+                None
+            };
+
+        self.state
+    }
+
+    /// Adds a piece to the board, clears any completed lines,
+    /// updates the score and level and moves on to the next piece.
+    /// The lines are cleared and the next piece spawned once the
+    /// line clear and entry delays are over.
+    fn lock_piece<R: Randomizer>(&mut self, piece: &Piece, randomizer: &mut R) {
+        // the corners have to be checked before any lines are cleared
+        let t_spin = Self::t_spin(piece, &self.board, self.last_movement);
+        self.last_t_spin = t_spin;
+
+        // add the piece to the board
+        let y_range = self.board.add_piece(piece);
+        self.render_info.newly_settled_pieces = Some(piece.position);
+        self.current_piece = *piece;
+
+        // lock out: the piece didn't make it down into the visible field
+        if piece.position.iter().all(|c| c.y >= self.board.visible_height() as i32) {
+            self.state = GameState::GameOver(TopOut::LockOut);
+            return;
+        }
+
+        // determine how many lines were cleared after adding this piece
+        let full_rows = self.board.full_rows(y_range);
+        let lines_cleared = full_rows.len() as u32;
+
+        let perfect_clear = lines_cleared > 0 && self.board.is_empty_apart_from(&full_rows);
+        let line_clear = self.score_line_clear(lines_cleared, t_spin, perfect_clear);
 
-        // Is the game over?
-        // We can tell this by whether or not the piece is overlapping with something on the board
-        // now. That should only be the case if we've spawned a new piece and it immediately
-        // settled.
-        if self.board.is_occupied(&self.current_piece.position) {
-            self.state = GameState::GameOver;
-            GameState::GameOver
+        self.statistics.pieces += 1;
+        self.statistics.lines += lines_cleared;
+        if perfect_clear {
+            self.statistics.perfect_clears += 1;
+        }
+
+        // only line clears count towards the next level, otherwise
+        // hard dropping would level up the game after a few pieces
+        let new_level = self.rules.level_after_lock(line_clear.as_ref(), self.level);
+        let off_to_a_new_level = new_level != self.level;
+        if off_to_a_new_level {
+            self.level = new_level;
+            self.fall_speed = self.gravity.gravity(self.level);
+        }
+        // save render info
+        // TODO: can we make the render info only get compiled if performing a
+        //       parial redraw?
+        self.render_info.new_level = if off_to_a_new_level { Some(self.level) } else { None };
+        // a new piece means hold is available again
+        self.can_hold = true;
+        self.full_rows = full_rows;
+        if !full_rows.is_empty() && self.line_clear_delay > 0 {
+            self.phase = Phase::LineClear;
+            self.phase_timer = self.line_clear_delay;
         } else {
-            self.state = GameState::Playing;
-            GameState::Playing
+            self.remove_full_rows();
+            self.start_entry_delay(randomizer);
         }
     }
 
-    /// Reports the current score.
-    pub fn score(&self) -> u32 {
-        self.score
+    /// Removes the rows filled by the last piece from the board.
+    fn remove_full_rows(&mut self) {
+        if !self.full_rows.is_empty() {
+            self.board.remove_rows(&self.full_rows);
+            self.full_rows = Default::default();
+            self.render_info.redraw_board = true;
+        }
     }
 
-    /// Reports the current level.
-    pub fn level(&self) -> u8 {
-        self.level as u8
+    /// Waits for the entry delay before spawning the next piece,
+    /// or spawns it straight away if there isn't one.
+    fn start_entry_delay<R: Randomizer>(&mut self, randomizer: &mut R) {
+        if self.are > 0 {
+            self.phase = Phase::Spawning;
+            self.phase_timer = self.are;
+        } else {
+            self.spawn_next_piece(randomizer);
+        }
     }
 
-    /// Draw the game state using the provided renderer.
-    pub fn draw<G: GameRenderer>(&self, renderer: &mut G) {
-        self._draw(renderer);
+    /// Takes the next piece from the queue and puts it into play.
+    fn spawn_next_piece<R: Randomizer>(&mut self, randomizer: &mut R) {
+        let next_piece = self.next_piece(randomizer);
+        self.spawn_piece(next_piece);
     }
 
-    #[cfg(not(any(feature="partial_redraw", feature="full_redraw")))]
-    compile_error!("Must enable feature \"partial_redraw\" or feature \"full_redraw\".");
-
-    #[cfg(all(feature="partial_redraw", feature="full_redraw"))]
-    compile_error!("feature \"partial_redraw\" and feature \"full_redraw\" cannot be enabled at the same time");
-
-    #[cfg(feature="partial_redraw")]
-    /// The function for drawing in the case of a renderer that performs partial redraws.
-    fn _draw<G: GameRenderer>(&self, renderer: &mut G) {
-
-        if let Some(score) = self.render_info.new_score {
-            renderer.draw_score(score);
+    /// Puts a new piece into play. Pieces spawn in the buffer zone just
+    /// above the visible field and move down a row straight away if they
+    /// can, or all the way onto the stack at 20G. Ends the game if the
+    /// piece overlaps the stack.
+    fn spawn_piece(&mut self, piece: Piece) {
+        self.phase = Phase::Falling;
+        self.current_piece = piece;
+        if self.board.is_occupied(&piece.position) {
+            self.state = GameState::GameOver(TopOut::BlockOut);
+        } else if self.fall_speed >= TWENTY_G {
+            self.current_piece = self.ghost_piece();
+        } else {
+            let (dropped_piece, _) = Self::handle_vertical_movement(&piece, &self.board, 1);
+            self.current_piece = dropped_piece;
         }
+        self.reset_lock_delay();
+        self.last_movement = MoveKind::Spawn;
+    }
 
-        if let Some(level) = self.render_info.new_level {
-            renderer.draw_level(level);
-        }
-        
-        // make updates to the board as necessary
-        if self.render_info.lines_cleared {
-            // redraw the board
-            for y in 0..22 {
-                for x in 0..10 {
-                    let real_y = 21 - y;
-                    renderer.draw_block(x as u8, real_y as u8, self.board.tetrimino_type_at(x, y));
-                }
+    /// Keeps track of combos and back-to-back clears, and awards the points
+    /// for a line clear or T-spin. Returns the line clear, if there was one.
+    fn score_line_clear(&mut self, lines: u32, t_spin: TSpin, perfect_clear: bool) -> Option<LineClear> {
+        if lines == 0 {
+            // any piece that doesn't clear a line ends the combo
+            self.combo = None;
+            if t_spin == TSpin::None {
+                return None;
             }
         } else {
-            // do all erasing first, in case a new piece may have some overlap with the
-            // previous position
-            if let Some(previous_pos) = &self.render_info.previous_piece_pos {
-                // erase the previous location
-                for c in previous_pos.iter() {
-                    let x = c.x;
-                    let y = 21 - c.y;
-                    renderer.draw_block(x as u8, y as u8, TetriminoType::EmptySpace);
-                }
-            }
+            self.combo = Some(self.combo.map_or(0, |combo| combo + 1));
+        }
 
-            // draw any newly settled pieces
-            if let Some (newly_settled_pieces) = &self.render_info.newly_settled_pieces {
-                for c in newly_settled_pieces.iter() {
-                    let x = c.x;
-                    let y = 21 - c.y;
-                    renderer.draw_block(x as u8, y as u8, self.board.tetrimino_type_at(c.x as u8, c.y as u8));
-                }
-            }
+        let mut line_clear = LineClear {
+            lines,
+            t_spin,
+            combo: self.combo.unwrap_or(0),
+            back_to_back: false,
+            perfect_clear,
+            points: 0,
+        };
+
+        // T-spins that don't clear lines neither start nor break a back-to-back chain
+        if lines > 0 {
+            let difficult = line_clear.is_difficult();
+            line_clear.back_to_back = difficult && self.back_to_back;
+            self.back_to_back = difficult;
         }
+        line_clear.points = self.rules.line_clear_points(&line_clear, self.level);
 
-        // draw the active (falling) piece
-        let tet_type =
-            match self.current_piece.piece_type {
-                PieceType::I(_) => TetriminoType::I,
-                PieceType::O    => TetriminoType::O,
-                PieceType::J    => TetriminoType::J,
-                PieceType::L    => TetriminoType::L,
-                PieceType::S    => TetriminoType::S,
-                PieceType::Z    => TetriminoType::Z,
-                PieceType::T    => TetriminoType::T,
-            };
-        for c in self.current_piece.position.iter() {
-            let x = c.x;
-            let y = 21 - c.y;
-            renderer.draw_block(x as u8, y as u8, tet_type);
+        self.score += line_clear.points;
+        self.last_line_clear = Some(line_clear);
+        self.render_info.line_clear_changed = true;
+        Some(line_clear)
+    }
+
+    /// Works out whether a piece was spun into place with the 3-corner rule:
+    /// a T piece whose last move was a rotation is a T-spin if at least three
+    /// of the four cells diagonal to its center are filled. It's only a mini
+    /// T-spin if one of the two corners the T is pointing at is empty, unless
+    /// the piece got there with the T-spin triple kick.
+    fn t_spin(piece: &Piece, board: &Board, last_movement: MoveKind) -> TSpin {
+        let kick = match last_movement {
+            MoveKind::Rotation { kick } if piece.piece_type == PieceType::T => kick,
+            _ => return TSpin::None,
+        };
+
+        // the front corners are the two the T is pointing at
+        let (front, back) = match piece.orientation {
+            Orientation::Spawn => ([(-1,  1), (1,  1)], [(-1, -1), ( 1, -1)]),
+            Orientation::Right => ([( 1,  1), (1, -1)], [(-1,  1), (-1, -1)]),
+            Orientation::Two   => ([(-1, -1), (1, -1)], [(-1,  1), ( 1,  1)]),
+            Orientation::Left  => ([(-1,  1), (-1, -1)], [( 1,  1), ( 1, -1)]),
+        };
+        // the center of the T is its first coordinate
+        let center = piece.position[0];
+        let filled = |corners: [(i32, i32); 2]| {
+            corners.iter().
+                filter(|&&(x, y)| board.is_blocked(center + Coord { x, y })).
+                count()
+        };
+        let (front, back) = (filled(front), filled(back));
+
+        if front + back < 3 {
+            TSpin::None
+        } else if front == 2 || kick == TST_KICK {
+            TSpin::Full
+        } else {
+            TSpin::Mini
         }
     }
 
+    /// Gives a newly spawned piece a fresh lock delay.
+    fn reset_lock_delay(&mut self) {
+        self.lowest_row = Self::lowest_row(&self.current_piece);
+        self.lock_timer = 0;
+        self.lock_resets = 0;
+    }
 
-    #[cfg(feature="full_redraw")]
-    /// The function for drawing in the case of a renderer that performs full redraws.
-    pub fn _draw<G: GameRenderer>(&self, renderer: &mut G) {
-        renderer.draw_board();
-        renderer.draw_score(self.score);
-        renderer.draw_level(self.level);
+    /// Returns the y-value of the lowest tetrimino in a piece.
+    fn lowest_row(piece: &Piece) -> i32 {
+        piece.position.iter().map(|c| c.y).min().unwrap_or(0)
+    }
 
-        // redraw the board
-        for y in 0..22 {
-            for x in 0..10 {
-                let real_y = 21 - y;
-                renderer.draw_block(x as u8, real_y as u8, self.board.tetrimino_type_at(x, y));
-            }
+    /// Returns `true` if the piece can't fall any further.
+    fn is_grounded(piece: &Piece, board: &Board) -> bool {
+        let (_, is_settled) = Self::handle_vertical_movement(piece, board, 1);
+        is_settled
+    }
+
+    /// Takes the next piece off the front of the queue and asks the
+    /// randomizer for a new one to put at the back.
+    fn next_piece<R: Randomizer>(&mut self, randomizer: &mut R) -> Piece {
+        let piece_type = self.next_queue[0];
+        self.next_queue.copy_within(1.., 0);
+        self.next_queue[MAX_PREVIEW_LENGTH - 1] = randomizer.next_piece();
+        self.render_info.next_changed = true;
+        Piece::spawn_on(piece_type, self.board.width() as usize, self.board.visible_height() as usize)
+    }
+
+    /// Calculates where the current piece would land if it were dropped straight down.
+    fn ghost_piece(&self) -> Piece {
+        let (ghost, _) = Self::handle_vertical_movement(&self.current_piece,
+                                                        &self.board,
+                                                        self.board.height());
+        ghost
+    }
+
+    /// Draws the current piece and its ghost. There's nothing
+    /// to draw while the game waits for the next piece.
+    fn draw_current_piece<G: GameRenderer>(&self, renderer: &mut G) {
+        if self.phase != Phase::Falling {
+            return;
         }
 
+        // always redraw the ghost, erasing the active piece may have erased part of it
+        self.draw_ghost(renderer);
+
         // draw the active (falling) piece
-        let tet_type =
-            match self.current_piece.piece_type {
-                PieceType::I(_) => TetriminoType::I,
-                PieceType::O    => TetriminoType::O,
-                PieceType::J    => TetriminoType::J,
-                PieceType::L    => TetriminoType::L,
-                PieceType::S    => TetriminoType::S,
-                PieceType::Z    => TetriminoType::Z,
-                PieceType::T    => TetriminoType::T,
-            };
+        let tet_type = TetriminoType::from(self.current_piece.piece_type);
         for c in self.current_piece.position.iter() {
             let x = c.x;
-            let y = 21 - c.y;
-            renderer.draw_block(x as u8, y as u8, tet_type);
+            if let Some(y) = self.screen_row(c.y) {
+                renderer.draw_block(x as u8, y, tet_type);
+            }
         }
     }
-}
-
-#[cfg(test)]
-/// Test methods
-mod tests {
-    use super::*;
-
-    #[test]
-    fn translation_allowed_when_predicate_yields_true() {
 
-        let piece = PIECE_TYPES[0];
+    /// Converts a row of the board to the row the renderer draws it on,
+    /// counting down from the top of the visible field. Rows in the
+    /// buffer zone above the visible field aren't drawn.
+    fn screen_row(&self, y: i32) -> Option<u8> {
+        let visible_height = self.board.visible_height() as i32;
+        if (0..visible_height).contains(&y) {
+            Some((visible_height - 1 - y) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Draws the ghost of the current piece.
+    fn draw_ghost<G: GameRenderer>(&self, renderer: &mut G) {
+        let tet_type = TetriminoType::from(self.current_piece.piece_type);
+        for c in self.ghost_piece().position.iter() {
+            let x = c.x;
+            if let Some(y) = self.screen_row(c.y) {
+                renderer.draw_ghost_block(x as u8, y, tet_type);
+            }
+        }
+    }
+
+    /// Reports the current score.
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Reports the current level.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Reports the upcoming pieces, in the order they will be played.
+    pub fn next_pieces(&self) -> impl Iterator<Item = TetriminoType> + '_ {
+        self.next_queue[..self.preview_length].iter().
+            map(|&piece_type| TetriminoType::from(piece_type))
+    }
+
+    /// Draws the preview of upcoming pieces.
+    fn draw_next_pieces<G: GameRenderer>(&self, renderer: &mut G) {
+        let mut next_pieces = [TetriminoType::EmptySpace; MAX_PREVIEW_LENGTH];
+        for (slot, piece) in next_pieces.iter_mut().zip(self.next_pieces()) {
+            *slot = piece;
+        }
+        renderer.draw_next(&next_pieces[..self.preview_length]);
+    }
+
+    /// Reports how many frames are left before the current piece locks,
+    /// or `None` if the piece isn't resting on anything.
+    /// Renderers can use this to flash the piece before it locks.
+    pub fn lock_delay_remaining(&self) -> Option<u32> {
+        if self.phase == Phase::Falling && Self::is_grounded(&self.current_piece, &self.board) {
+            Some(self.lock_delay.saturating_sub(self.lock_timer))
+        } else {
+            None
+        }
+    }
+
+    /// Reports what the game is doing this frame.
+    pub fn phase(&self) -> Phase {
+        match self.phase {
+            Phase::Falling if Self::is_grounded(&self.current_piece, &self.board) => Phase::Locking,
+            phase => phase,
+        }
+    }
+
+    /// Reports how many frames are left in the line clear or entry delay,
+    /// or `None` if a piece is in play.
+    pub fn delay_remaining(&self) -> Option<u32> {
+        match self.phase {
+            Phase::LineClear | Phase::Spawning => Some(self.phase_timer),
+            _ => None,
+        }
+    }
+
+    /// Reports the rows that are about to be cleared, using the same
+    /// y-values as `GameRenderer::draw_block`. This is empty unless the
+    /// game is in the `LineClear` phase.
+    pub fn rows_being_cleared(&self) -> impl Iterator<Item = u8> + '_ {
+        let rows = if self.phase == Phase::LineClear { self.full_rows.as_slice() } else { &[] };
+        rows.iter().filter_map(move |&y| self.screen_row(y as i32))
+    }
+
+    /// Reports how many more times moving or rotating the current piece
+    /// will restart its lock delay.
+    pub fn lock_resets_remaining(&self) -> u32 {
+        MAX_LOCK_RESETS - self.lock_resets
+    }
+
+    /// Changes how held left and right inputs repeat.
+    pub fn set_handling(&mut self, handling: Handling) {
+        self.handling = handling;
+    }
+
+    /// Reports the last thing that moved the current piece.
+    pub fn last_move(&self) -> MoveKind {
+        self.last_movement
+    }
+
+    /// Reports whether the last piece to lock was a T-spin, a mini T-spin or
+    /// neither, whether or not it cleared any lines.
+    pub fn last_t_spin(&self) -> TSpin {
+        self.last_t_spin
+    }
+
+    /// Reports the rules the game is being scored by.
+    pub fn rules(&self) -> &S {
+        &self.rules
+    }
+
+    /// Reports the running totals for the current game.
+    pub fn statistics(&self) -> Statistics {
+        self.statistics
+    }
+
+    /// Reports the last line clear or T-spin, with the points it was worth.
+    pub fn last_line_clear(&self) -> Option<LineClear> {
+        self.last_line_clear
+    }
+
+    /// Gives read-only access to the blocks that have locked onto the board.
+    /// The current piece isn't part of the board until it locks.
+    pub fn board(&self) -> BoardView<'_> {
+        BoardView::new(&self.board)
+    }
+
+    /// Reports the piece in the hold slot, if any.
+    pub fn held_piece(&self) -> Option<TetriminoType> {
+        self.held_piece.map(|p| TetriminoType::from(p.piece_type))
+    }
+
+    /// Draw the game state using the provided renderer.
+    pub fn draw<G: GameRenderer>(&self, renderer: &mut G) {
+        self._draw(renderer);
+    }
+
+    #[cfg(not(any(feature="partial_redraw", feature="full_redraw")))]
+    compile_error!("Must enable feature \"partial_redraw\" or feature \"full_redraw\".");
+
+    #[cfg(all(feature="partial_redraw", feature="full_redraw"))]
+    compile_error!("feature \"partial_redraw\" and feature \"full_redraw\" cannot be enabled at the same time");
+
+    #[cfg(feature="partial_redraw")]
+    /// The function for drawing in the case of a renderer that performs partial redraws.
+    fn _draw<G: GameRenderer>(&self, renderer: &mut G) {
+
+        if let Some(score) = self.render_info.new_score {
+            renderer.draw_score(score);
+        }
+
+        if let Some(level) = self.render_info.new_level {
+            renderer.draw_level(level);
+        }
+
+        if self.render_info.hold_changed {
+            renderer.draw_hold(self.held_piece());
+        }
+
+        if self.render_info.next_changed {
+            self.draw_next_pieces(renderer);
+        }
+
+        if self.render_info.line_clear_changed {
+            renderer.draw_line_clear(self.last_line_clear);
+        }
+        
+        // make updates to the board as necessary
+        if self.render_info.redraw_board {
+            // redraw the board
+            for y in 0..self.board.visible_height() as u8 {
+                for x in 0..self.board.width() as u8 {
+                    let real_y = self.board.visible_height() as u8 - 1 - y;
+                    renderer.draw_block(x, real_y, self.board.tetrimino_type_at(x, y));
+                }
+            }
+        } else {
+            // do all erasing first, in case a new piece may have some overlap with the
+            // previous position
+            if let Some(previous_pos) = &self.render_info.previous_piece_pos {
+                // erase the previous location
+                for c in previous_pos.iter() {
+                    let x = c.x;
+                    if let Some(y) = self.screen_row(c.y) {
+                        renderer.draw_block(x as u8, y, TetriminoType::EmptySpace);
+                    }
+                }
+            }
+
+            if let Some(previous_ghost_pos) = &self.render_info.previous_ghost_pos {
+                // erase the previous ghost before drawing any newly settled pieces,
+                // a hard dropped piece settles right where its ghost was
+                for c in previous_ghost_pos.iter() {
+                    let x = c.x;
+                    if let Some(y) = self.screen_row(c.y) {
+                        renderer.draw_block(x as u8, y, TetriminoType::EmptySpace);
+                    }
+                }
+            }
+
+            // draw any newly settled pieces
+            if let Some (newly_settled_pieces) = &self.render_info.newly_settled_pieces {
+                for c in newly_settled_pieces.iter() {
+                    let x = c.x;
+                    if let Some(y) = self.screen_row(c.y) {
+                        renderer.draw_block(x as u8, y, self.board.tetrimino_type_at(c.x as u8, c.y as u8));
+                    }
+                }
+            }
+        }
+
+        self.draw_current_piece(renderer);
+    }
+
+
+    #[cfg(feature="full_redraw")]
+    /// The function for drawing in the case of a renderer that performs full redraws.
+    pub fn _draw<G: GameRenderer>(&self, renderer: &mut G) {
+        renderer.draw_board();
+        renderer.draw_score(self.score);
+        renderer.draw_level(self.level);
+        renderer.draw_hold(self.held_piece());
+        self.draw_next_pieces(renderer);
+        renderer.draw_line_clear(self.last_line_clear);
+
+        // redraw the board
+        for y in 0..self.board.visible_height() as u8 {
+            for x in 0..self.board.width() as u8 {
+                let real_y = self.board.visible_height() as u8 - 1 - y;
+                renderer.draw_block(x, real_y, self.board.tetrimino_type_at(x, y));
+            }
+        }
+
+        self.draw_current_piece(renderer);
+    }
+}
+
+#[cfg(test)]
+/// Test methods
+mod tests {
+    use super::*;
+    use crate::pieces::{Orientation, PIECE_TYPES};
+    use crate::scoring::{self, Nes, COMBO_POINTS, HARD_DROP_POINTS, SOFT_DROP_POINTS};
+
+    // the tests play by the classic rules unless they say otherwise
+    type Game = super::Game<Classic>;
+    use crate::input::Action;
+
+    #[test]
+    fn translation_allowed_when_predicate_yields_true() {
+
+        let piece = PIECE_TYPES[0];
 
         let all_translations_allowed = |_p: &Piece| true;
 
@@ -510,6 +1342,8 @@ mod tests {
             down: false,
             cw_rotate: false,
             ccw_rotate: false,
+            hard_drop: false,
+            hold: false,
         };
 
         let updated_piece = Game::handle_horizontal_input(&input, &piece, all_translations_allowed).unwrap();
@@ -531,6 +1365,8 @@ mod tests {
             down: false,
             cw_rotate: false,
             ccw_rotate: false,
+            hard_drop: false,
+            hold: false,
         };
 
         let updated_piece = Game::handle_horizontal_input(&input, &piece, no_translation_allowed);
@@ -550,6 +1386,8 @@ mod tests {
             down: false,
             cw_rotate: false,
             ccw_rotate: false,
+            hard_drop: false,
+            hold: false,
         };
 
         let updated_piece = Game::handle_horizontal_input(&input, &piece, all_translation_allowed);
@@ -569,6 +1407,8 @@ mod tests {
             down: false,
             cw_rotate: true,
             ccw_rotate: false,
+            hard_drop: false,
+            hold: false,
         };
 
         let updated_piece = Game::handle_rotation_input(&input, &piece, no_rotation_allowed);
@@ -593,9 +1433,11 @@ mod tests {
             down: false,
             cw_rotate: true,
             ccw_rotate: false,
+            hard_drop: false,
+            hold: false,
         };
 
-        let updated_piece = Game::handle_rotation_input(&input, &piece, all_rotation_allowed).unwrap();
+        let (updated_piece, _) = Game::handle_rotation_input(&input, &piece, all_rotation_allowed).unwrap();
 
         // don't check the rotated value, the rotations are verified in other tests
         // just make sure that the rotated value differs from the original value
@@ -614,6 +1456,8 @@ mod tests {
             down: false,
             cw_rotate: true,
             ccw_rotate: true,
+            hard_drop: false,
+            hold: false,
         };
 
         let updated_piece = Game::handle_rotation_input(&input, &piece, all_rotation_allowed);
@@ -621,6 +1465,54 @@ mod tests {
         assert_eq!(updated_piece, None);
     }
 
+    #[test]
+    fn rotation_kicks_off_the_wall() {
+        let board = Board::new();
+        let valid_piece_location = |p: &Piece| board.fits(&p.position);
+
+        // stand a T piece up and push it against the left wall
+        let mut piece = PIECE_TYPES[6].cw_rot();
+        for _ in 0..4 {
+            piece = piece.move_left();
+        }
+        assert_eq!(piece.position[0].x, 0);
+
+        let input = Input {
+            ccw_rotate: true,
+            .. Default::default()
+        };
+
+        // rotating in place would push the piece through the wall,
+        // so the piece has to be kicked one cell to the right
+        let (updated_piece, kick) = Game::handle_rotation_input(&input, &piece, valid_piece_location).unwrap();
+
+        assert_eq!(kick, 1);
+        assert_eq!(updated_piece.position, PIECE_TYPES[6].move_left().move_left().move_left().position);
+    }
+
+    #[test]
+    fn rotation_kicks_off_the_floor() {
+        let board = Board::new();
+        let valid_piece_location = |p: &Piece| board.fits(&p.position);
+
+        // a T piece lying flat on the floor
+        let (piece, _) = Game::handle_vertical_movement(&PIECE_TYPES[6], &board, board.height());
+        assert!(piece.position.iter().any(|c| c.y == 0));
+
+        let input = Input {
+            cw_rotate: true,
+            .. Default::default()
+        };
+
+        // rotating in place, or kicking left, would put a block below
+        // the floor, so SRS kicks the piece up and to the left
+        let (updated_piece, kick) = Game::handle_rotation_input(&input, &piece, valid_piece_location).unwrap();
+
+        assert_eq!(kick, 2);
+        assert_eq!(updated_piece, piece.cw_rot_kicks()[2]);
+        assert!(updated_piece.position.iter().all(|c| c.y >= 0));
+    }
+
     #[test]
     fn fast_moving_piece_settles_appropriately() {
         let mut board = Board::new();
@@ -637,7 +1529,7 @@ mod tests {
         let (updated_piece, is_settled) = Game::handle_vertical_movement(&piece, &board, displacement);
 
         // check to see if the piece settled
-        assert_eq!(is_settled, true);
+        assert!(is_settled);
 
         // check to see if it settled in the right location
         assert_eq!(updated_piece.position[0].y, 20);
@@ -646,26 +1538,23 @@ mod tests {
         assert_eq!(updated_piece.position[3].y, 20);
     }
 
-    /// Rng implementation that doesn't shuffle anything
+    /// Randomizer that deals the pieces in order, picking up
+    /// where the queue of `Game::new_test` leaves off
     struct Randy {
-        indexes: [usize; 7],
         i: usize,
     }
 
     impl Randy {
         fn new() -> Randy {
-            Randy {
-                indexes: [0, 1, 2, 3, 4, 5, 6],
-                i: 0,
-            }
+            Randy { i: 0 }
         }
     }
 
-    impl Rng for Randy {
-        fn next(&mut self) -> usize {
-            let r = self.indexes[self.i];
-            self.i = (self.i + 1) % 6;
-            r
+    impl Randomizer for Randy {
+        fn next_piece(&mut self) -> PieceType {
+            let piece_type = PIECE_TYPES[self.i].piece_type;
+            self.i = (self.i + 1) % PIECE_TYPES.len();
+            piece_type
         }
     }
 
@@ -689,14 +1578,953 @@ mod tests {
         input.left = true;
 
         // run one more iteration of the main loop
-        // the piece should be settled after this
-        // iteration
+        // to move the piece left
         let _ = game.run_loop(&input, &mut randy);
 
+        // the piece should be settled once the lock delay is over
+        for _ in 0..game.lock_delay {
+            let _ = game.run_loop(&Default::default(), &mut randy);
+        }
+
         // make sure the piece has been added to the playfield
         assert_ne!(game.board.tetrimino_type_at(5, 0), TetriminoType::EmptySpace);
         assert_ne!(game.board.tetrimino_type_at(4, 0), TetriminoType::EmptySpace);
         assert_ne!(game.board.tetrimino_type_at(3, 0), TetriminoType::EmptySpace);
         assert_ne!(game.board.tetrimino_type_at(2, 0), TetriminoType::EmptySpace);
     }
+
+    /// Returns where a piece ends up on the frame it spawns, a row down
+    /// from its spawn location in the buffer zone.
+    fn spawned(piece: Piece) -> Piece {
+        piece.apply_gravity(1)
+    }
+
+    #[test]
+    fn hard_drop_locks_the_piece_in_one_frame() {
+        let mut game = Game::new_test();
+
+        let input = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+
+        let mut randy = Randy::new();
+
+        // the I piece spawns on row 20 and drops all the way to row 0
+        let _ = game.run_loop(&input, &mut randy);
+
+        assert_ne!(game.board.tetrimino_type_at(3, 0), TetriminoType::EmptySpace);
+        assert_ne!(game.board.tetrimino_type_at(4, 0), TetriminoType::EmptySpace);
+        assert_ne!(game.board.tetrimino_type_at(5, 0), TetriminoType::EmptySpace);
+        assert_ne!(game.board.tetrimino_type_at(6, 0), TetriminoType::EmptySpace);
+
+        // the next piece should be in play
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[1]));
+
+        assert_eq!(game.score(), 20 * HARD_DROP_POINTS);
+        // drop points shouldn't count towards the next level
+        assert_eq!(game.level(), 1);
+    }
+
+    #[test]
+    fn hold_swaps_pieces_once_per_drop() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        let hold = Input {
+            hold: true,
+            .. Default::default()
+        };
+
+        // nothing is held yet, so the I piece is held and the O piece comes out of the bag
+        let _ = game.run_loop(&hold, &mut randy);
+        assert_eq!(game.held_piece(), Some(TetriminoType::I));
+        assert_eq!(game.current_piece.piece_type, PIECE_TYPES[1].piece_type);
+
+        // hold can't be used again until the O piece locks
+        let _ = game.run_loop(&hold, &mut randy);
+        assert_eq!(game.held_piece(), Some(TetriminoType::I));
+        assert_eq!(game.current_piece.piece_type, PIECE_TYPES[1].piece_type);
+
+        let hard_drop = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        // move the J piece around before swapping it out
+        let rotate = Input {
+            cw_rotate: true,
+            left: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&rotate, &mut randy);
+
+        let _ = game.run_loop(&hold, &mut randy);
+        // the J piece goes back to its spawn location in the hold slot
+        assert_eq!(game.held_piece, Some(PIECE_TYPES[2]));
+        // and the I piece comes back at its spawn location
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[0]));
+    }
+
+    #[test]
+    fn preview_looks_past_the_end_of_the_bag() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        let expected: [TetriminoType; MAX_PREVIEW_LENGTH] = [
+            TetriminoType::O,
+            TetriminoType::J,
+            TetriminoType::L,
+            TetriminoType::S,
+            TetriminoType::Z,
+            TetriminoType::T,
+        ];
+        assert!(game.next_pieces().eq(expected.iter().copied()));
+
+        let hard_drop = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+
+        // play up to the T piece, the last piece of the first bag
+        for _ in 0..MAX_PREVIEW_LENGTH {
+            let _ = game.run_loop(&hard_drop, &mut randy);
+            // release the button so the next hard drop is a fresh press
+            let _ = game.run_loop(&Default::default(), &mut randy);
+        }
+        assert_eq!(game.current_piece.piece_type, PIECE_TYPES[6].piece_type);
+
+        // the whole preview should come from the next bag
+        let next_bag: [TetriminoType; MAX_PREVIEW_LENGTH] = [
+            TetriminoType::I,
+            TetriminoType::O,
+            TetriminoType::J,
+            TetriminoType::L,
+            TetriminoType::S,
+            TetriminoType::Z,
+        ];
+        assert!(game.next_pieces().eq(next_bag.iter().copied()));
+    }
+
+    #[test]
+    fn preview_length_is_clamped() {
+        let mut randy = Randy::new();
+
+        let game = Game::with_config(&mut randy, GameConfig { preview_length: 0, .. Default::default() });
+        assert_eq!(game.next_pieces().count(), 1);
+
+        let game = Game::with_config(&mut randy, GameConfig { preview_length: 10, .. Default::default() });
+        assert_eq!(game.next_pieces().count(), MAX_PREVIEW_LENGTH);
+    }
+
+    #[test]
+    fn ghost_lands_on_the_stack() {
+        let mut game = Game::new_test();
+
+        for x in 0..5 {
+            game.board.add_tetrimino_at(x, 0, TetriminoType::O);
+            game.board.add_tetrimino_at(x, 1, TetriminoType::O);
+        }
+
+        // the I piece spans x = 3..=6, so it lands on the columns at x = 3 and 4
+        let ghost = game.ghost_piece();
+        assert!(ghost.position.iter().all(|c| c.y == 2));
+        assert_eq!(ghost.piece_type, game.current_piece.piece_type);
+    }
+
+    /// Renderer that keeps track of where the ghost was drawn
+    struct Recorder {
+        ghost_blocks: [[bool; 10]; DEFAULT_VISIBLE_HEIGHT],
+    }
+
+    impl Recorder {
+        fn new() -> Recorder {
+            Recorder {
+                ghost_blocks: Default::default(),
+            }
+        }
+    }
+
+    impl GameRenderer for Recorder {
+        #[cfg(feature="full_redraw")]
+        fn draw_board(&mut self) {
+            *self = Recorder::new();
+        }
+
+        fn draw_block(&mut self, x: u8, y: u8, _piece_type: TetriminoType) {
+            self.ghost_blocks[y as usize][x as usize] = false;
+        }
+
+        fn draw_ghost_block(&mut self, x: u8, y: u8, _piece_type: TetriminoType) {
+            self.ghost_blocks[y as usize][x as usize] = true;
+        }
+
+        fn draw_score(&mut self, _score: u32) {}
+        fn draw_level(&mut self, _level: usize) {}
+        fn draw_hold(&mut self, _piece_type: Option<TetriminoType>) {}
+        fn draw_next(&mut self, _piece_types: &[TetriminoType]) {}
+        fn draw_line_clear(&mut self, _line_clear: Option<LineClear>) {}
+    }
+
+    #[test]
+    fn ghost_follows_the_piece() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        let mut recorder = Recorder::new();
+
+        let left = Input {
+            left: true,
+            .. Default::default()
+        };
+
+        game.draw(&mut recorder);
+        let _ = game.run_loop(&left, &mut randy);
+        game.draw(&mut recorder);
+
+        // the I piece moved from x = 3..=6 to x = 2..=5 and the ghost should follow
+        // NOTE: the renderer's y coordinates are flipped
+        for x in 0..10 {
+            assert_eq!(recorder.ghost_blocks[DEFAULT_VISIBLE_HEIGHT - 1][x], (2..=5).contains(&x), "x = {}", x);
+        }
+    }
+
+    /// Soft drops the current piece until it's resting on the bottom of the board.
+    fn drop_to_the_bottom(game: &mut Game, randy: &mut Randy) {
+        let down = Input {
+            down: true,
+            .. Default::default()
+        };
+
+        while game.lock_delay_remaining().is_none() {
+            let _ = game.run_loop(&down, randy);
+        }
+    }
+
+    #[test]
+    fn pieces_on_the_floor_dont_fall_through_it() {
+        let board = Board::new();
+        let (piece, is_settled) = Game::handle_vertical_movement(&PIECE_TYPES[6], &board, board.height());
+        assert!(is_settled);
+        assert_eq!(piece.position.iter().map(|c| c.y).min(), Some(0));
+
+        // moving a piece that is already on the floor leaves it there
+        for displacement in [1, board.height()] {
+            assert_eq!(Game::handle_vertical_movement(&piece, &board, displacement), (piece, true));
+        }
+
+        // even one already poking through the floor doesn't keep going
+        let below_the_floor = piece.apply_gravity(1);
+        assert_eq!(Game::handle_vertical_movement(&below_the_floor, &board, board.height()),
+                   (below_the_floor, true));
+    }
+
+    /// Puts a T piece on the floor and turns it clockwise, which kicks it
+    /// up off the floor.
+    fn floor_kicked_t(game: &mut Game, randy: &mut Randy) {
+        let (t_piece, _) = Game::handle_vertical_movement(&PIECE_TYPES[6], &game.board, game.board.height());
+        game.current_piece = t_piece;
+        let _ = game.run_loop(&Input { cw_rotate: true, .. Default::default() }, randy);
+        let _ = game.run_loop(&Default::default(), randy);
+        assert_eq!(game.last_movement, MoveKind::Rotation { kick: 2 });
+    }
+
+    #[test]
+    fn hard_drop_after_a_floor_kick_locks_on_the_floor() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        floor_kicked_t(&mut game, &mut randy);
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::Playing);
+
+        assert_eq!(game.statistics().pieces, 1);
+        assert_eq!(game.board.tetrimino_type_at(3, 0), TetriminoType::T);
+        assert_eq!(game.board.tetrimino_type_at(3, 2), TetriminoType::T);
+    }
+
+    #[test]
+    fn gravity_after_a_floor_kick_locks_on_the_floor() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        floor_kicked_t(&mut game, &mut randy);
+
+        for _ in 0..game.lock_delay + 60 {
+            assert_eq!(game.run_loop(&Default::default(), &mut randy), GameState::Playing);
+        }
+
+        assert_eq!(game.statistics().pieces, 1);
+        assert_eq!(game.board.tetrimino_type_at(3, 0), TetriminoType::T);
+        assert_eq!(game.board.tetrimino_type_at(3, 2), TetriminoType::T);
+    }
+
+    #[test]
+    fn piece_locks_after_the_lock_delay() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        drop_to_the_bottom(&mut game, &mut randy);
+        assert_eq!(game.lock_delay_remaining(), Some(game.lock_delay - 1));
+
+        for _ in 1..game.lock_delay {
+            assert_eq!(game.current_piece.piece_type, PIECE_TYPES[0].piece_type);
+            let _ = game.run_loop(&Default::default(), &mut randy);
+        }
+
+        // the I piece is locked and the O piece is in play
+        assert_ne!(game.board.tetrimino_type_at(3, 0), TetriminoType::EmptySpace);
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[1]));
+    }
+
+    #[test]
+    fn moving_on_the_ground_resets_the_lock_delay_a_limited_number_of_times() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        drop_to_the_bottom(&mut game, &mut randy);
+
+        let left = Input { left: true, .. Default::default() };
+        let right = Input { right: true, .. Default::default() };
+
+        // move back and forth, each move should restart the lock delay
+        for i in 0..MAX_LOCK_RESETS {
+            let input = if i % 2 == 0 { &left } else { &right };
+            let _ = game.run_loop(input, &mut randy);
+            assert_eq!(game.lock_delay_remaining(), Some(game.lock_delay - 1));
+        }
+        assert_eq!(game.lock_resets_remaining(), 0);
+
+        // once the resets are used up, moving doesn't buy any more time
+        let remaining = game.lock_delay_remaining().unwrap();
+        let _ = game.run_loop(&left, &mut randy);
+        assert_eq!(game.lock_delay_remaining(), Some(remaining - 1));
+    }
+
+    #[test]
+    fn falling_to_a_new_lowest_row_resets_the_lock_delay() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        // leave a gap at the right edge for the I piece to fall into
+        for x in 0..8 {
+            game.board.add_tetrimino_at(x, 0, TetriminoType::O);
+        }
+
+        drop_to_the_bottom(&mut game, &mut randy);
+        assert_eq!(Game::lowest_row(&game.current_piece), 1);
+
+        let left = Input { left: true, .. Default::default() };
+        let right = Input { right: true, .. Default::default() };
+        for _ in 0..4 {
+            let _ = game.run_loop(&left, &mut randy);
+            let _ = game.run_loop(&right, &mut randy);
+        }
+        assert!(game.lock_resets_remaining() < MAX_LOCK_RESETS);
+
+        // stand the piece up and slide it into the gap
+        let rotate = Input { cw_rotate: true, .. Default::default() };
+        let _ = game.run_loop(&rotate, &mut randy);
+        while game.current_piece.position[0].x < 9 {
+            let _ = game.run_loop(&right, &mut randy);
+        }
+        drop_to_the_bottom(&mut game, &mut randy);
+
+        assert_eq!(Game::lowest_row(&game.current_piece), 0);
+        assert_eq!(game.lock_resets_remaining(), MAX_LOCK_RESETS);
+    }
+
+    #[test]
+    fn held_direction_waits_for_das_then_repeats_at_arr() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        game.set_handling(Handling { das: 4, arr: 2 });
+
+        let left = Input { left: true, .. Default::default() };
+
+        // the I piece starts at x = 3..=6
+        let mut xs = [0; 9];
+        for x in xs.iter_mut() {
+            let _ = game.run_loop(&left, &mut randy);
+            *x = game.current_piece.position[3].x;
+        }
+
+        // one move on the press, nothing until DAS charges on the 4th frame
+        // after that, a move every other frame until the wall
+        assert_eq!(xs, [2, 2, 2, 2, 1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn zero_arr_moves_the_piece_to_the_wall() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        game.set_handling(Handling { das: 1, arr: 0 });
+
+        let right = Input { right: true, .. Default::default() };
+
+        let _ = game.run_loop(&right, &mut randy);
+        assert_eq!(game.current_piece.position[0].x, 7);
+
+        let _ = game.run_loop(&right, &mut randy);
+        assert_eq!(game.current_piece.position[0].x, 9);
+    }
+
+    #[test]
+    fn das_stays_charged_for_the_next_piece() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        game.set_handling(Handling { das: 2, arr: 0 });
+
+        let right = Input { right: true, .. Default::default() };
+        for _ in 0..3 {
+            let _ = game.run_loop(&right, &mut randy);
+        }
+
+        let right_and_drop = Input { right: true, hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&right_and_drop, &mut randy);
+
+        // the O piece should be moved to the wall as soon as it's in play
+        assert_eq!(game.current_piece.piece_type, PIECE_TYPES[1].piece_type);
+        let _ = game.run_loop(&right, &mut randy);
+        assert_eq!(game.current_piece.position[3].x, 9);
+    }
+
+    #[test]
+    fn holding_a_rotation_button_only_rotates_once() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        let rotate = Input { cw_rotate: true, .. Default::default() };
+
+        let _ = game.run_loop(&rotate, &mut randy);
+        let _ = game.run_loop(&rotate, &mut randy);
+        assert_eq!(game.current_piece.orientation, Orientation::Right);
+
+        // releasing and pressing again rotates again
+        let _ = game.run_loop(&Default::default(), &mut randy);
+        let _ = game.run_loop(&rotate, &mut randy);
+        assert_eq!(game.current_piece.orientation, Orientation::Two);
+    }
+
+    #[test]
+    fn holding_hard_drop_only_drops_one_piece() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        // the O piece is still at the top of the board
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[1]));
+    }
+
+    #[test]
+    fn input_events_drive_the_game() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        // a tap that starts and ends between two frames still moves the piece
+        game.push_input_event(InputEvent { action: Action::Right, pressed: true, timestamp: 100 });
+        game.push_input_event(InputEvent { action: Action::Right, pressed: false, timestamp: 105 });
+        let _ = game.step(&mut randy);
+        assert_eq!(game.current_piece.position, PIECE_TYPES[0].move_right().position);
+
+        game.push_input_event(InputEvent { action: Action::HardDrop, pressed: true, timestamp: 120 });
+        let _ = game.step(&mut randy);
+        assert!(game.controls().held(Action::HardDrop));
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[1]));
+
+        // nothing new has happened, so the O piece stays put
+        let _ = game.step(&mut randy);
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[1]));
+    }
+
+    /// Builds a T-spin double slot at the bottom of the board,
+    /// with an overhang over the left side.
+    fn build_t_spin_double_slot(board: &mut Board) {
+        for x in 0..10 {
+            if x != 4 {
+                board.add_tetrimino_at(x, 0, TetriminoType::I);
+            }
+            if !(3..=5).contains(&x) {
+                board.add_tetrimino_at(x, 1, TetriminoType::I);
+            }
+        }
+        board.add_tetrimino_at(3, 2, TetriminoType::I);
+    }
+
+    #[test]
+    fn t_spin_double_is_announced_and_scored() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        build_t_spin_double_slot(&mut game.board);
+
+        // a T pointing right, just above the slot
+        game.current_piece = PIECE_TYPES[6].cw_rot().apply_gravity(19);
+
+        let spin_and_drop = Input {
+            cw_rotate: true,
+            hard_drop: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&spin_and_drop, &mut randy);
+
+        assert_eq!(game.last_t_spin(), TSpin::Full);
+        let line_clear = game.last_line_clear().unwrap();
+        assert_eq!(line_clear.lines, 2);
+        assert_eq!(line_clear.t_spin, TSpin::Full);
+        assert_eq!(line_clear.name(), "T-SPIN DOUBLE");
+        assert_eq!(game.score(), 1200);
+    }
+
+    #[test]
+    fn t_spin_needs_a_rotation_and_three_corners() {
+        let mut board = Board::new();
+        // only one of the corners above the T is filled, the floor fills the others
+        board.add_tetrimino_at(0, 1, TetriminoType::I);
+
+        let piece = PIECE_TYPES[6].move_left().move_left().move_left().apply_gravity(20);
+        assert_eq!(piece.position[0], Coord { x: 1, y: 0 });
+
+        assert_eq!(Game::t_spin(&piece, &board, MoveKind::Rotation { kick: 0 }), TSpin::Mini);
+        assert_eq!(Game::t_spin(&piece, &board, MoveKind::Rotation { kick: TST_KICK }), TSpin::Full);
+        assert_eq!(Game::t_spin(&piece, &board, MoveKind::Shift), TSpin::None);
+        assert_eq!(Game::t_spin(&piece, &Board::new(), MoveKind::Rotation { kick: 0 }), TSpin::None);
+    }
+
+    #[test]
+    fn back_to_back_and_combo_bonuses() {
+        let mut game = Game::new_test();
+
+        game.score_line_clear(4, TSpin::None, false);
+        assert_eq!(game.score(), 800);
+
+        // back-to-back tetris, and the second clear of a combo
+        game.score_line_clear(4, TSpin::None, false);
+        let line_clear = game.last_line_clear().unwrap();
+        assert!(line_clear.back_to_back);
+        assert_eq!(line_clear.combo, 1);
+        assert_eq!(line_clear.points, 1200 + COMBO_POINTS);
+
+        // a single breaks the back-to-back chain but not the combo
+        game.score_line_clear(1, TSpin::None, false);
+        let line_clear = game.last_line_clear().unwrap();
+        assert!(!line_clear.back_to_back);
+        assert_eq!(line_clear.points, 100 + 2 * COMBO_POINTS);
+
+        // a piece that doesn't clear anything ends the combo
+        game.score_line_clear(0, TSpin::None, false);
+        game.score_line_clear(1, TSpin::None, false);
+        assert_eq!(game.last_line_clear().unwrap().combo, 0);
+    }
+
+    #[test]
+    fn soft_drop_earns_a_point_per_row() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        let soft_drop = Input {
+            down: true,
+            .. Default::default()
+        };
+        for _ in 0..3 {
+            let _ = game.run_loop(&soft_drop, &mut randy);
+        }
+
+        assert_eq!(game.score(), 3 * SOFT_DROP_POINTS);
+    }
+
+    #[test]
+    fn dropping_into_a_t_slot_is_not_a_t_spin() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        build_t_spin_double_slot(&mut game.board);
+
+        // a T already pointing down, sitting in the slot
+        game.current_piece = PIECE_TYPES[6].cw_rot().cw_rot().apply_gravity(19);
+        let _ = game.run_loop(&Default::default(), &mut randy);
+        assert_eq!(game.last_move(), MoveKind::Spawn);
+
+        // let it lock on its own
+        while game.last_line_clear().is_none() {
+            let _ = game.run_loop(&Default::default(), &mut randy);
+        }
+
+        // the slot has 3 corners filled, but the piece wasn't rotated
+        assert_eq!(game.last_t_spin(), TSpin::None);
+        assert_eq!(game.last_line_clear().unwrap().name(), "DOUBLE");
+    }
+
+    #[test]
+    fn perfect_clear_is_detected_and_counted() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        // a row with a gap that fits the I piece lying flat
+        for x in 4..10 {
+            game.board.add_tetrimino_at(x, 0, TetriminoType::O);
+        }
+        game.current_piece = game.current_piece.move_left().move_left().move_left();
+
+        let hard_drop = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        let line_clear = game.last_line_clear().unwrap();
+        assert!(line_clear.perfect_clear);
+        assert_eq!(line_clear.points, 100 + scoring::perfect_clear_points(1, false));
+        assert_eq!(game.statistics(), Statistics { pieces: 1, lines: 1, perfect_clears: 1 });
+    }
+
+    #[test]
+    fn scoring_rules_can_be_swapped() {
+        let mut randy = Randy::new();
+        let mut game = super::Game::with_rules(&mut randy, Default::default(), Nes::default());
+        assert_eq!(game.level(), 0);
+
+        // a row with a gap that fits the I piece lying flat
+        for x in 4..10 {
+            game.board.add_tetrimino_at(x, 0, TetriminoType::O);
+        }
+        game.current_piece = Piece::spawn(PieceType::I).move_left().move_left().move_left();
+
+        let hard_drop = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        // no points for the hard drop, 40 for the single at level 0
+        assert_eq!(game.score(), 40);
+    }
+
+    #[test]
+    fn twenty_g_pieces_spawn_on_the_stack() {
+        let mut randy = Randy::new();
+        let config = GameConfig { gravity: GravityCurve::TwentyG, .. Default::default() };
+        let mut game = super::Game::with_config(&mut randy, config);
+
+        assert_eq!(Game::lowest_row(&game.current_piece), 0);
+
+        // the next piece lands on top of the first one as soon as it spawns
+        let hard_drop = Input {
+            hard_drop: true,
+            .. Default::default()
+        };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        assert!(Game::is_grounded(&game.current_piece, &game.board));
+        assert_eq!(game.current_piece.position, game.ghost_piece().position);
+    }
+
+    #[test]
+    fn gravity_follows_the_curve() {
+        let mut randy = Randy::new();
+        let config = GameConfig { gravity: GravityCurve::Table(&[CELL / 2]), .. Default::default() };
+        let mut game = super::Game::with_config(&mut randy, config);
+        let start = Game::lowest_row(&game.current_piece);
+
+        for _ in 0..4 {
+            let _ = game.run_loop(&Default::default(), &mut randy);
+        }
+        assert_eq!(Game::lowest_row(&game.current_piece), start - 2);
+    }
+
+    #[test]
+    fn update_runs_whole_frames() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        let start = Game::lowest_row(&game.current_piece);
+
+        // a second at 144Hz is 60 frames, a row falls every 60 frames at level 1
+        for _ in 0..143 {
+            let _ = game.update(Duration::from_nanos(1_000_000_000 / 144), &mut randy);
+        }
+        assert_eq!(Game::lowest_row(&game.current_piece), start);
+        let _ = game.update(Duration::from_millis(7), &mut randy);
+        assert_eq!(Game::lowest_row(&game.current_piece), start - 1);
+    }
+
+    #[test]
+    fn update_doesnt_catch_up_after_a_stall() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        let start = Game::lowest_row(&game.current_piece);
+
+        let _ = game.update(Duration::from_secs(10), &mut randy);
+        assert_eq!(Game::lowest_row(&game.current_piece), start - 1);
+        assert_eq!(game.unsimulated_time, 0);
+    }
+
+    #[test]
+    fn frames_between_draws_are_merged() {
+        let first = RenderInfo { previous_piece_pos: Some([Coord { x: 1, y: 1 }; 4]), .. Default::default() };
+        let second = RenderInfo { previous_piece_pos: Some([Coord { x: 2, y: 2 }; 4]), .. Default::default() };
+
+        let merged = first.merge(second);
+        assert!(merged.redraw_board);
+        assert_eq!(merged.previous_piece_pos, Some([Coord { x: 1, y: 1 }; 4]));
+
+        let merged = RenderInfo { hold_changed: true, .. Default::default() }.merge(Default::default());
+        assert!(merged.hold_changed);
+        assert!(!merged.redraw_board);
+    }
+
+    #[test]
+    fn full_rows_stay_until_the_line_clear_delay_is_over() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        game.line_clear_delay = 3;
+        game.are = 2;
+
+        for x in 4..10 {
+            game.board.add_tetrimino_at(x, 0, TetriminoType::O);
+        }
+        game.current_piece = game.current_piece.move_left().move_left().move_left();
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        // the clear is scored straight away, but the row is still there
+        assert_eq!(game.phase(), Phase::LineClear);
+        assert_eq!(game.last_line_clear().unwrap().lines, 1);
+        assert!(game.rows_being_cleared().eq([DEFAULT_VISIBLE_HEIGHT as u8 - 1]));
+        assert_ne!(game.board.tetrimino_type_at(0, 0), TetriminoType::EmptySpace);
+
+        let nothing = Input::default();
+        for remaining in (1..3).rev() {
+            let _ = game.run_loop(&nothing, &mut randy);
+            assert_eq!(game.delay_remaining(), Some(remaining));
+        }
+
+        // the row goes once the delay is over, then the entry delay starts
+        let _ = game.run_loop(&nothing, &mut randy);
+        assert_eq!(game.phase(), Phase::Spawning);
+        assert_eq!(game.board.tetrimino_type_at(0, 0), TetriminoType::EmptySpace);
+        assert_eq!(game.rows_being_cleared().count(), 0);
+
+        let _ = game.run_loop(&nothing, &mut randy);
+        assert_eq!(game.phase(), Phase::Spawning);
+        let _ = game.run_loop(&nothing, &mut randy);
+        assert_eq!(game.phase(), Phase::Falling);
+        assert_eq!(game.current_piece.piece_type, PieceType::O);
+    }
+
+    #[test]
+    fn phase_is_locking_while_the_piece_is_on_the_ground() {
+        let mut game = Game::new_test();
+        assert_eq!(game.phase(), Phase::Falling);
+        assert_eq!(game.delay_remaining(), None);
+
+        game.current_piece = game.ghost_piece();
+        assert_eq!(game.phase(), Phase::Locking);
+    }
+
+    #[test]
+    fn das_charges_during_the_entry_delay() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        game.are = 5;
+        game.set_handling(Handling { das: 3, arr: 0 });
+
+        let right_and_drop = Input { right: true, hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&right_and_drop, &mut randy);
+        assert_eq!(game.phase(), Phase::Spawning);
+
+        // the piece spawns at the end of the delay, where it was supposed to
+        let right = Input { right: true, .. Default::default() };
+        for _ in 0..5 {
+            let _ = game.run_loop(&right, &mut randy);
+        }
+        assert_eq!(game.phase(), Phase::Falling);
+        assert_eq!(game.current_piece, spawned(Piece::spawn(PieceType::O)));
+
+        // and is shifted to the wall on its first frame
+        let _ = game.run_loop(&right, &mut randy);
+        assert_eq!(game.current_piece.position[3].x, 9);
+    }
+
+    #[test]
+    fn pieces_move_down_into_view_when_they_spawn() {
+        let mut randy = Randy::new();
+        let game = super::Game::with_config(&mut randy, Default::default());
+
+        // the I piece spawns in the buffer zone, just above the visible field
+        assert!(Piece::spawn(PieceType::I).position.iter().all(|c| c.y == DEFAULT_VISIBLE_HEIGHT as i32));
+        assert!(game.current_piece.position.iter().all(|c| c.y == DEFAULT_VISIBLE_HEIGHT as i32 - 1));
+    }
+
+    #[test]
+    fn spawning_on_the_stack_is_a_block_out() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        // the O piece is next, in the way of where it spawns
+        game.board.add_tetrimino_at(4, DEFAULT_VISIBLE_HEIGHT, TetriminoType::J);
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::GameOver(TopOut::BlockOut));
+        // nothing happens once the game is over
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::GameOver(TopOut::BlockOut));
+        assert_eq!(game.statistics().pieces, 1);
+    }
+
+    #[test]
+    fn locking_above_the_visible_field_is_a_lock_out() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        // the stack under the I piece reaches the top of the visible field
+        for y in 0..DEFAULT_VISIBLE_HEIGHT {
+            for x in 3..7 {
+                game.board.add_tetrimino_at(x, y, TetriminoType::J);
+            }
+        }
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::GameOver(TopOut::LockOut));
+    }
+
+    #[test]
+    fn nothing_moves_while_paused() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+
+        game.pause();
+        assert_eq!(game.state(), GameState::Paused);
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::Paused);
+        assert_eq!(game.update(Duration::from_secs(5), &mut randy), GameState::Paused);
+        assert_eq!(game.current_piece, Piece::spawn(PieceType::I));
+        assert_eq!(game.statistics().pieces, 0);
+
+        // the time spent paused isn't made up for afterwards
+        game.resume();
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.unsimulated_time, 0);
+        // hard drop was pressed during the pause, it has to be pressed again
+        assert_eq!(game.run_loop(&hard_drop, &mut randy), GameState::Playing);
+        assert_eq!(game.statistics().pieces, 0);
+        let _ = game.run_loop(&Input::default(), &mut randy);
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        assert_eq!(game.statistics().pieces, 1);
+    }
+
+    #[test]
+    fn resuming_redraws_everything_on_the_next_draw() {
+        let mut randy = Randy::new();
+
+        // whether or not a whole frame runs before the draw
+        for dt in [Duration::from_millis(1), Duration::from_millis(20)] {
+            let mut game = Game::new_test();
+            game.pause();
+            game.resume();
+
+            let _ = game.update(dt, &mut randy);
+            assert!(game.render_info.redraw_board);
+            assert_eq!(game.render_info.new_score, Some(0));
+
+            // once it's been handed over it isn't drawn again
+            let _ = game.update(Duration::from_millis(20), &mut randy);
+            assert!(!game.render_info.redraw_board);
+        }
+    }
+
+    #[test]
+    fn levels_are_reported_past_255() {
+        let mut game = Game::new_test();
+        game.level = 300;
+        assert_eq!(game.level(), 300);
+    }
+
+    #[test]
+    fn game_over_cant_be_paused() {
+        let mut game = Game::new_test();
+        game.state = GameState::GameOver(TopOut::BlockOut);
+
+        game.pause();
+        assert_eq!(game.state(), GameState::GameOver(TopOut::BlockOut));
+        game.resume();
+        assert_eq!(game.state(), GameState::GameOver(TopOut::BlockOut));
+    }
+
+    #[test]
+    fn reset_starts_over_with_the_same_settings() {
+        let mut randy = Randy::new();
+        let config = GameConfig { preview_length: 3, are: 4, .. Default::default() };
+        let mut game = super::Game::with_rules(&mut randy, config, Nes::default());
+        let handling = Handling { das: 6, arr: 1 };
+        game.set_handling(handling);
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        game.pause();
+
+        game.reset(&mut Randy::new());
+        assert_eq!(game.state(), GameState::Playing);
+        assert_eq!(game.statistics(), Statistics::default());
+        assert!(game.board.is_empty_apart_from(&Default::default()));
+        assert_eq!(game.level(), 0);
+        assert_eq!(game.current_piece, spawned(PIECE_TYPES[0]));
+        assert_eq!(game.config(), GameConfig { handling, .. config });
+    }
+
+    #[test]
+    fn an_i_piece_clears_a_line_on_a_four_wide_board() {
+        let mut randy = Randy::new();
+        let config = GameConfig { board_width: 4, .. Default::default() };
+        let mut game = super::Game::with_config(&mut randy, config);
+        assert_eq!(game.current_piece.piece_type, PieceType::I);
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        assert_eq!(game.statistics().lines, 1);
+        assert!(game.last_line_clear().unwrap().perfect_clear);
+    }
+
+    #[test]
+    fn boards_can_be_wide_and_tall() {
+        let mut randy = Randy::new();
+        let config = GameConfig { board_width: 20, visible_height: 30, .. Default::default() };
+        let mut game = super::Game::with_config(&mut randy, config);
+
+        // the I piece spawns in the middle, just above the 30 visible rows
+        assert!(game.current_piece.position.iter().all(|c| (8..12).contains(&c.x) && c.y == 29));
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+        assert_eq!(game.score(), 29 * HARD_DROP_POINTS);
+        assert_eq!(game.statistics().lines, 0);
+    }
+
+    #[test]
+    fn board_size_is_clamped() {
+        let mut randy = Randy::new();
+        let config = GameConfig { board_width: 100, visible_height: 0, .. Default::default() };
+        let game = super::Game::with_config(&mut randy, config);
+
+        assert_eq!(game.config().board_width, MAX_BOARD_WIDTH);
+        assert_eq!(game.config().visible_height, 4);
+    }
+
+    #[test]
+    fn locked_pieces_can_be_read_from_the_board() {
+        let mut game = Game::new_test();
+        let mut randy = Randy::new();
+        assert!(game.board().is_empty());
+
+        let hard_drop = Input { hard_drop: true, .. Default::default() };
+        let _ = game.run_loop(&hard_drop, &mut randy);
+
+        // the I piece lies flat across x = 3..=6 on the floor
+        let board = game.board();
+        assert_eq!((board.width(), board.visible_height()), (10, 20));
+        assert!(board.column_heights().eq([0, 0, 0, 1, 1, 1, 1, 0, 0, 0]));
+        assert_eq!(board.row_fill_count(0), 4);
+        assert_eq!(board.cell(3, 0), TetriminoType::I);
+        // the O piece in play isn't on the board yet
+        assert_eq!(board.row_fill_count(DEFAULT_VISIBLE_HEIGHT - 1), 0);
+    }
 }
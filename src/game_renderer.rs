@@ -1,6 +1,9 @@
-#[derive(Copy, Clone, Debug, PartialEq)]
+use crate::scoring::LineClear;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 /// Defines the different Tetrimino states for use by the renderer.
 pub enum TetriminoType {
+    #[default]
     EmptySpace,
     I,
     O,
@@ -11,16 +14,20 @@ pub enum TetriminoType {
     T,
 }
 
-impl Default for TetriminoType {
-    fn default() -> Self { TetriminoType::EmptySpace }
-}
-
 /// Define a trait for drawing the game state.
 /// This allows the use of multiple backends.
 pub trait GameRenderer {
     #[cfg(feature="full_redraw")]
     fn draw_board(&mut self);
     fn draw_block(&mut self, x: u8, y: u8, piece_type: TetriminoType);
+    /// Draws one block of the ghost piece, i.e., where the current piece will land.
+    fn draw_ghost_block(&mut self, x: u8, y: u8, piece_type: TetriminoType);
     fn draw_score(&mut self, score: u32);
     fn draw_level(&mut self, level: usize);
+    /// Draws the piece in the hold slot, if there is one.
+    fn draw_hold(&mut self, piece_type: Option<TetriminoType>);
+    /// Draws the upcoming pieces, starting with the next one to be played.
+    fn draw_next(&mut self, piece_types: &[TetriminoType]);
+    /// Draws the last line clear or T-spin, e.g. "T-SPIN DOUBLE".
+    fn draw_line_clear(&mut self, line_clear: Option<LineClear>);
 }
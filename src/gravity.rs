@@ -0,0 +1,181 @@
+/// One cell of gravity. Gravity is measured in 1/65536 of a cell per
+/// frame, the same units Tetris Worlds uses for its G values, so the
+/// game never needs floating point math while pieces fall.
+pub const CELL : u32 = 1 << 16;
+
+/// 1 unit of gravity = moving one cell
+/// these are the gravity constants for 60 fps
+/// source: https://harddrop.com/wiki/Tetris_Worlds
+/// Each value is rounded to the 1/65536 that drops the piece on the same
+/// frames as the floating point value fourtris used to accumulate.
+const CLASSIC_GRAVITY : [u32; 15] = [
+    1093,   // 0.01667
+    1377,   // 0.021017
+    1768,   // 0.026977
+    2317,   // 0.035356
+    3075,   // 0.04693
+    4169,   // 0.06361
+    5761,   // 0.0879
+    8100,   // 0.1236
+    11633,  // 0.1775
+    17026,  // 0.2598
+    25428,  // 0.388
+    38666,  // 0.59
+    60294,  // 0.92
+    95683,  // 1.46
+    154664, // 2.36
+];
+
+/// The number of frames the NES version takes to move a piece down one
+/// cell, starting at level 0. Level 29 and above use the last entry.
+const NES_FRAMES_PER_CELL : [u32; 30] = [
+    48, 43, 38, 33, 28, 23, 18, 13, 8, 6,
+    5, 5, 5, 4, 4, 4, 3, 3, 3, 2,
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 1,
+];
+
+/// Enough gravity to drop a piece from the top of the board to the
+/// bottom in a single frame.
+pub const TWENTY_G : u32 = 20 * CELL;
+
+/// The frame rate the gravity values are given for.
+const FRAMES_PER_SECOND : f32 = 60.0;
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// How fast pieces fall at each level.
+pub enum GravityCurve {
+    /// The Tetris Worlds table fourtris has always used, for levels 1 to 15.
+    /// Higher levels keep the speed of level 15.
+    #[default]
+    Classic,
+    /// The guideline formula, `(0.8 - (level - 1) * 0.007)^(level - 1)`
+    /// seconds per cell. It works for any level, and reaches 20G at level 19.
+    Guideline,
+    /// The NES speeds, starting at level 0.
+    Nes,
+    /// Pieces drop to the stack as soon as they spawn, at every level.
+    TwentyG,
+    /// A custom table in 1/65536 of a cell per frame. The first entry is for
+    /// level 0, and levels past the end of the table use the last entry.
+    Table(&'static [u32]),
+}
+
+impl GravityCurve {
+    /// Returns how far a piece falls each frame at a level,
+    /// in 1/65536 of a cell.
+    pub fn gravity(&self, level: usize) -> u32 {
+        match *self {
+            GravityCurve::Classic => {
+                let i = level.clamp(1, CLASSIC_GRAVITY.len()) - 1;
+                CLASSIC_GRAVITY[i]
+            },
+            GravityCurve::Guideline => {
+                // this is the only curve that needs floating point math,
+                // the game only works it out when the level changes
+                let level = level.max(1);
+                let base = 0.8 - (level - 1) as f32 * 0.007;
+                // no powi in core, so multiply it out
+                let mut seconds_per_cell = 1.0;
+                for _ in 1..level {
+                    seconds_per_cell *= base;
+                }
+                let cells_per_frame = 1.0 / (seconds_per_cell * FRAMES_PER_SECOND);
+                // the formula speeds back up past level 114, where the base goes negative
+                if base <= 0.0 || cells_per_frame >= (TWENTY_G / CELL) as f32 {
+                    TWENTY_G
+                } else {
+                    (cells_per_frame * CELL as f32) as u32
+                }
+            },
+            GravityCurve::Nes => {
+                let i = level.min(NES_FRAMES_PER_CELL.len() - 1);
+                // round up so a cell takes exactly the right number of frames
+                CELL.div_ceil(NES_FRAMES_PER_CELL[i])
+            },
+            GravityCurve::TwentyG => TWENTY_G,
+            GravityCurve::Table(table) => {
+                match table.get(level).or(table.last()) {
+                    Some(&gravity) => gravity,
+                    None => 0,
+                }
+            },
+        }
+    }
+
+    /// Returns `true` if pieces drop all the way down as soon as they spawn.
+    pub fn is_twenty_g(&self, level: usize) -> bool {
+        self.gravity(level) >= TWENTY_G
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_holds_at_level_15() {
+        assert_eq!(GravityCurve::Classic.gravity(1), 1093);
+        assert_eq!(GravityCurve::Classic.gravity(15), 154664);
+        assert_eq!(GravityCurve::Classic.gravity(30), 154664);
+    }
+
+    #[test]
+    fn classic_drops_on_the_same_frames_as_before() {
+        const OLD_GRAVITY : [f32; 15] = [
+            0.01667, 0.021017, 0.026977, 0.035356, 0.04693,
+            0.06361, 0.0879, 0.1236, 0.1775, 0.2598,
+            0.388, 0.59, 0.92, 1.46, 2.36,
+        ];
+
+        for (level, &old_gravity) in (1..).zip(OLD_GRAVITY.iter()) {
+            let gravity = GravityCurve::Classic.gravity(level);
+            let mut old_displacement = 0.0f32;
+            let mut displacement = 0u32;
+
+            // longer than it takes to fall the height of the board at level 1
+            for frame in 0..300 {
+                old_displacement += old_gravity;
+                let old_cells = old_displacement as u32;
+                old_displacement -= old_cells as f32;
+
+                displacement += gravity;
+                let cells = displacement / CELL;
+                displacement -= cells * CELL;
+
+                assert_eq!(cells, old_cells, "level {} frame {}", level, frame);
+            }
+        }
+    }
+
+    #[test]
+    fn guideline_formula_matches_the_classic_table() {
+        // the Tetris Worlds table was worked out from the same formula
+        for (i, &expected) in CLASSIC_GRAVITY.iter().enumerate() {
+            let gravity = GravityCurve::Guideline.gravity(i + 1);
+            assert!(gravity.abs_diff(expected) * 100 < expected, "level {}: {}", i + 1, gravity);
+        }
+
+        assert!(!GravityCurve::Guideline.is_twenty_g(18));
+        assert!(GravityCurve::Guideline.is_twenty_g(19));
+        assert!(GravityCurve::Guideline.is_twenty_g(200));
+    }
+
+    #[test]
+    fn nes_uses_frames_per_cell() {
+        // a piece at level 0 moves down a cell every 48 frames
+        let gravity = GravityCurve::Nes.gravity(0);
+        assert!(47 * gravity < CELL && 48 * gravity >= CELL);
+
+        assert_eq!(GravityCurve::Nes.gravity(19), CELL / 2);
+        assert_eq!(GravityCurve::Nes.gravity(40), CELL);
+    }
+
+    #[test]
+    fn tables_hold_their_last_value() {
+        const TABLE : [u32; 2] = [CELL / 2, CELL];
+
+        assert_eq!(GravityCurve::Table(&TABLE).gravity(0), CELL / 2);
+        assert_eq!(GravityCurve::Table(&TABLE).gravity(5), CELL);
+        assert_eq!(GravityCurve::Table(&[]).gravity(5), 0);
+    }
+}
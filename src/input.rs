@@ -0,0 +1,265 @@
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// Represents the different types of user input possible.
+/// This is a snapshot of which buttons are down during a frame.
+pub struct Input {
+    /// true when user attempts to move the piece left
+    pub left: bool,
+    /// true when user attempts to move the piece right
+    pub right: bool,
+    /// true when user attempts to move the piece down
+    pub down: bool,
+    /// true when user wishes to rotate a piece clockwise
+    pub cw_rotate: bool,
+    /// true when user wishes to rotate a piece counterclockwise
+    pub ccw_rotate: bool,
+    /// true when user wishes to drop the piece to the bottom and lock it
+    pub hard_drop: bool,
+    /// true when user wishes to swap the current piece with the held piece
+    pub hold: bool,
+}
+
+impl Input {
+    /// Returns whether the button for an action is down.
+    fn get(&self, action: Action) -> bool {
+        match action {
+            Action::Left      => self.left,
+            Action::Right     => self.right,
+            Action::Down      => self.down,
+            Action::CwRotate  => self.cw_rotate,
+            Action::CcwRotate => self.ccw_rotate,
+            Action::HardDrop  => self.hard_drop,
+            Action::Hold      => self.hold,
+        }
+    }
+
+    /// Builds an `Input` from a function reporting the state of each action.
+    fn from_fn<F: Fn(Action) -> bool>(f: F) -> Input {
+        Input {
+            left: f(Action::Left),
+            right: f(Action::Right),
+            down: f(Action::Down),
+            cw_rotate: f(Action::CwRotate),
+            ccw_rotate: f(Action::CcwRotate),
+            hard_drop: f(Action::HardDrop),
+            hold: f(Action::Hold),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The actions a player can perform. There is one for each field of `Input`.
+pub enum Action {
+    Left,
+    Right,
+    Down,
+    CwRotate,
+    CcwRotate,
+    HardDrop,
+    Hold,
+}
+
+const ACTIONS : [Action; 7] = [
+    Action::Left,
+    Action::Right,
+    Action::Down,
+    Action::CwRotate,
+    Action::CcwRotate,
+    Action::HardDrop,
+    Action::Hold,
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// A single button press or release, for frontends that get
+/// discrete key events rather than polling the keyboard.
+pub struct InputEvent {
+    pub action: Action,
+    /// true when the button went down, false when it came back up
+    pub pressed: bool,
+    /// When the event happened, in milliseconds. Any starting point
+    /// will do, it's only used to put the events in order.
+    pub timestamp: u32,
+}
+
+/// The number of events that can be waiting for the next frame.
+/// If more events than this arrive, the oldest ones are applied early.
+const EVENT_QUEUE_SIZE : usize = 32;
+
+#[derive(Default)]
+/// Tracks the state of every action from one frame to the next, so
+/// the game can tell a fresh press from a button that is being held.
+pub struct InputTracker {
+    /// Which buttons are down right now.
+    down: [bool; ACTIONS.len()],
+    /// Which buttons went down since the last frame.
+    pressed: [bool; ACTIONS.len()],
+    /// Which buttons came up since the last frame.
+    released: [bool; ACTIONS.len()],
+    /// Events waiting to be applied at the start of the next frame, each
+    /// with the order it was queued in to break ties between timestamps.
+    events: [Option<(InputEvent, u32)>; EVENT_QUEUE_SIZE],
+    /// The sequence number to give the next queued event.
+    next_sequence: u32,
+}
+
+impl InputTracker {
+    pub fn new() -> InputTracker {
+        Default::default()
+    }
+
+    /// Updates the state of every action from a snapshot of the buttons.
+    pub fn set(&mut self, input: &Input) {
+        for action in ACTIONS {
+            self.set_action(action, input.get(action));
+        }
+    }
+
+    /// Queues a button press or release to be applied at the start of the next frame.
+    pub fn push_event(&mut self, event: InputEvent) {
+        let queued = Some((event, self.next_sequence));
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        if let Some(slot) = self.events.iter_mut().find(|e| e.is_none()) {
+            *slot = queued;
+        } else {
+            // the queue is full, make room by applying the oldest event now
+            let oldest = self.oldest_event().unwrap();
+            let (oldest_event, _) = self.events[oldest].take().unwrap();
+            self.set_action(oldest_event.action, oldest_event.pressed);
+            self.events[oldest] = queued;
+        }
+    }
+
+    /// Applies all the queued events in the order they happened.
+    pub(crate) fn apply_events(&mut self) {
+        while let Some(oldest) = self.oldest_event() {
+            let (event, _) = self.events[oldest].take().unwrap();
+            self.set_action(event.action, event.pressed);
+        }
+    }
+
+    /// Forgets which buttons were pressed and released, ready for the next frame.
+    pub(crate) fn end_frame(&mut self) {
+        self.pressed = Default::default();
+        self.released = Default::default();
+    }
+
+    /// Returns `true` if the button for the action went down since the last frame.
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed[action as usize]
+    }
+
+    /// Returns `true` if the button for the action is down.
+    pub fn held(&self, action: Action) -> bool {
+        self.down[action as usize]
+    }
+
+    /// Returns `true` if the button for the action came up since the last frame.
+    pub fn released(&self, action: Action) -> bool {
+        self.released[action as usize]
+    }
+
+    /// Returns the actions that went down since the last frame.
+    pub(crate) fn pressed_input(&self) -> Input {
+        Input::from_fn(|action| self.pressed(action))
+    }
+
+    /// Returns the actions that are active this frame. A button that was
+    /// tapped between two frames counts as active even though it's no
+    /// longer held, otherwise very short taps would get lost.
+    pub(crate) fn active_input(&self) -> Input {
+        Input::from_fn(|action| self.held(action) || self.pressed(action))
+    }
+
+    /// Finds the index of the queued event with the earliest timestamp.
+    /// Events with the same timestamp come out in the order they were queued,
+    /// whichever slots they ended up in.
+    fn oldest_event(&self) -> Option<usize> {
+        self.events.iter().enumerate().
+            filter_map(|(i, e)| e.map(|(e, sequence)| (i, (e.timestamp, sequence)))).
+            min_by_key(|&(_, order)| order).
+            map(|(i, _)| i)
+    }
+
+    fn set_action(&mut self, action: Action, is_down: bool) {
+        let i = action as usize;
+        if is_down && !self.down[i] {
+            self.pressed[i] = true;
+        } else if !is_down && self.down[i] {
+            self.released[i] = true;
+        }
+        self.down[i] = is_down;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_report_presses_and_releases() {
+        let mut tracker = InputTracker::new();
+        let rotate = Input { cw_rotate: true, .. Default::default() };
+
+        tracker.set(&rotate);
+        assert!(tracker.pressed(Action::CwRotate));
+        assert!(tracker.held(Action::CwRotate));
+        tracker.end_frame();
+
+        tracker.set(&rotate);
+        assert!(!tracker.pressed(Action::CwRotate));
+        assert!(tracker.held(Action::CwRotate));
+        tracker.end_frame();
+
+        tracker.set(&Default::default());
+        assert!(!tracker.held(Action::CwRotate));
+        assert!(tracker.released(Action::CwRotate));
+    }
+
+    #[test]
+    fn events_are_applied_in_timestamp_order() {
+        let mut tracker = InputTracker::new();
+
+        // delivered out of order, the release really happened last
+        tracker.push_event(InputEvent { action: Action::Left, pressed: false, timestamp: 20 });
+        tracker.push_event(InputEvent { action: Action::Left, pressed: true, timestamp: 10 });
+        tracker.apply_events();
+
+        assert!(tracker.pressed(Action::Left));
+        assert!(tracker.released(Action::Left));
+        assert!(!tracker.held(Action::Left));
+        // the tap still counts for this frame
+        assert!(tracker.active_input().left);
+    }
+
+    #[test]
+    fn full_queue_applies_the_oldest_event() {
+        let mut tracker = InputTracker::new();
+
+        for i in 0..EVENT_QUEUE_SIZE as u32 + 1 {
+            tracker.push_event(InputEvent { action: Action::Down, pressed: i % 2 == 0, timestamp: i });
+        }
+
+        // the first press was applied to make room
+        assert!(tracker.pressed(Action::Down));
+
+        tracker.apply_events();
+        // the last event was a press
+        assert!(tracker.held(Action::Down));
+    }
+
+    #[test]
+    fn events_with_the_same_timestamp_keep_their_order_after_overflow() {
+        let mut tracker = InputTracker::new();
+
+        for _ in 0..EVENT_QUEUE_SIZE - 1 {
+            tracker.push_event(InputEvent { action: Action::Down, pressed: true, timestamp: 0 });
+        }
+        tracker.push_event(InputEvent { action: Action::Left, pressed: true, timestamp: 10 });
+        // the queue is full, so the release takes the first slot back
+        tracker.push_event(InputEvent { action: Action::Left, pressed: false, timestamp: 10 });
+        tracker.apply_events();
+
+        assert!(tracker.pressed(Action::Left));
+        assert!(tracker.released(Action::Left));
+        assert!(!tracker.held(Action::Left));
+    }
+}
@@ -1,7 +1,14 @@
 #![no_std]
 pub mod game;
 pub mod game_renderer;
+pub mod gravity;
+pub mod input;
+pub mod metrics;
+pub mod randomizer;
 pub mod rng;
+pub mod scoring;
 mod board;
 mod coord;
 mod pieces;
+
+pub use board::{BitBoard, FullRows, MAX_BOARD_HEIGHT};
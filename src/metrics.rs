@@ -0,0 +1,255 @@
+use crate::board::{BoardView, MAX_BOARD_WIDTH};
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// The usual features used to judge how good a stack is, all worked out
+/// at once. Lower is better for everything apart from `tetris_ready`.
+pub struct Metrics {
+    /// The sum of the heights of every column.
+    pub aggregate_height: usize,
+    /// The number of empty cells with a filled cell above them.
+    pub holes: usize,
+    /// The number of filled cells with a hole below them.
+    pub covered_cells: usize,
+    /// The sum of the height differences between neighbouring columns.
+    pub bumpiness: usize,
+    /// The number of filled/empty changes along the rows.
+    pub row_transitions: usize,
+    /// The number of filled/empty changes up the columns.
+    pub column_transitions: usize,
+    /// The number of columns that are lower than both their neighbours.
+    pub wells: usize,
+    /// The depth of the deepest well.
+    pub max_well_depth: usize,
+    /// Whether an I piece dropped into a well would clear four lines.
+    pub tetris_ready: bool,
+}
+
+impl Metrics {
+    /// Measures the stack on a board.
+    pub fn new(board: &BoardView) -> Metrics {
+        let heights = column_heights(board);
+        let heights = &heights[..board.width()];
+        Metrics {
+            aggregate_height: aggregate_height(heights),
+            holes: holes(board, heights),
+            covered_cells: covered_cells(board, heights),
+            bumpiness: bumpiness(heights),
+            row_transitions: row_transitions(board, heights),
+            column_transitions: column_transitions(board, heights),
+            wells: wells(heights),
+            max_well_depth: max_well_depth(heights),
+            tetris_ready: is_tetris_ready(board, heights),
+        }
+    }
+}
+
+/// Returns the height of every column, so they only have to be found once.
+/// Only the first `board.width()` entries are used; the rest are 0.
+/// The other functions here take that slice of the heights.
+pub fn column_heights(board: &BoardView) -> [usize; MAX_BOARD_WIDTH] {
+    let mut heights = [0; MAX_BOARD_WIDTH];
+    for (height, column_height) in heights.iter_mut().zip(board.column_heights()) {
+        *height = column_height;
+    }
+    heights
+}
+
+/// Returns the sum of the heights of every column.
+pub fn aggregate_height(heights: &[usize]) -> usize {
+    heights.iter().sum()
+}
+
+/// Returns the number of empty cells with a filled cell somewhere above
+/// them in the same column.
+pub fn holes(board: &BoardView, heights: &[usize]) -> usize {
+    heights.iter().enumerate().map(|(x, &height)| {
+        (0..height).filter(|&y| !board.is_filled(x, y)).count()
+    }).sum()
+}
+
+/// Returns the number of filled cells that have a hole somewhere below
+/// them in the same column. These have to be cleared to get at the holes.
+pub fn covered_cells(board: &BoardView, heights: &[usize]) -> usize {
+    heights.iter().enumerate().map(|(x, &height)| {
+        let lowest_hole = (0..height).find(|&y| !board.is_filled(x, y));
+        match lowest_hole {
+            Some(hole) => (hole..height).filter(|&y| board.is_filled(x, y)).count(),
+            None => 0,
+        }
+    }).sum()
+}
+
+/// Returns the sum of the differences in height between neighbouring columns.
+pub fn bumpiness(heights: &[usize]) -> usize {
+    heights.windows(2).map(|pair| pair[0].abs_diff(pair[1])).sum()
+}
+
+/// Returns the number of times a filled cell sits next to an empty one
+/// along a row, counting the walls as filled. Only rows with blocks in
+/// them are counted, so an empty board has no transitions.
+pub fn row_transitions(board: &BoardView, heights: &[usize]) -> usize {
+    let stack_height = heights.iter().copied().max().unwrap_or(0);
+    (0..stack_height).map(|y| {
+        // the walls on both sides are filled
+        let cells = core::iter::once(true).
+            chain((0..heights.len()).map(|x| board.is_filled(x, y))).
+            chain(core::iter::once(true));
+        transitions(cells)
+    }).sum()
+}
+
+/// Returns the number of times a filled cell sits above or below an empty
+/// one in a column, counting the floor as filled. Only the cells up to the
+/// top of each column are counted, so an empty board has no transitions.
+pub fn column_transitions(board: &BoardView, heights: &[usize]) -> usize {
+    heights.iter().enumerate().map(|(x, &height)| {
+        let cells = core::iter::once(true).
+            chain((0..height).map(|y| board.is_filled(x, y)));
+        transitions(cells)
+    }).sum()
+}
+
+/// Counts the changes between filled and empty in a line of cells.
+fn transitions<I: Iterator<Item = bool> + Clone>(cells: I) -> usize {
+    cells.clone().zip(cells.skip(1)).filter(|(a, b)| a != b).count()
+}
+
+/// Returns how deep a column is as a well: how far it is below the lower
+/// of its neighbours, counting the walls as infinitely tall.
+/// Columns that aren't below both neighbours have a depth of 0.
+pub fn well_depth(heights: &[usize], x: usize) -> usize {
+    let neighbour_height = |x: Option<usize>| match x {
+        Some(x) if x < heights.len() => heights[x],
+        _ => usize::MAX,
+    };
+    let lowest_neighbour = core::cmp::min(neighbour_height(x.checked_sub(1)),
+                                          neighbour_height(Some(x + 1)));
+    if x >= heights.len() || lowest_neighbour == usize::MAX {
+        // a column with walls on both sides isn't a well
+        0
+    } else {
+        lowest_neighbour.saturating_sub(heights[x])
+    }
+}
+
+/// Returns the number of columns that are wells.
+pub fn wells(heights: &[usize]) -> usize {
+    (0..heights.len()).filter(|&x| well_depth(heights, x) > 0).count()
+}
+
+/// Returns the depth of the deepest well, or 0 if there are none.
+pub fn max_well_depth(heights: &[usize]) -> usize {
+    (0..heights.len()).map(|x| well_depth(heights, x)).max().unwrap_or(0)
+}
+
+/// Returns `true` if dropping an I piece straight down into a well
+/// would clear four lines.
+pub fn is_tetris_ready(board: &BoardView, heights: &[usize]) -> bool {
+    heights.iter().enumerate().any(|(x, &bottom)| {
+        // the four rows above the bottom of the column are full apart from it
+        (bottom..bottom + 4).all(|y| {
+            !board.is_filled(x, y) && board.row_fill_count(y) == heights.len() - 1
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::game_renderer::TetriminoType;
+
+    /// Builds a board from rows of text, the top row first.
+    /// `#` is a filled cell and anything else is empty.
+    fn board_from(rows: &[&str]) -> Board {
+        let mut board = Board::with_size(rows[0].len(), 20);
+        for (y, row) in rows.iter().rev().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                if cell == '#' {
+                    board.add_tetrimino_at(x, y, TetriminoType::O);
+                }
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn empty_board_has_no_features() {
+        let board = Board::with_size(10, 20);
+        let metrics = Metrics::new(&BoardView::new(&board));
+
+        assert_eq!(metrics, Metrics::default());
+    }
+
+    #[test]
+    fn heights_holes_and_bumpiness() {
+        let board = board_from(&[
+            ".#..",
+            "##.#",
+            "#..#",
+        ]);
+        let view = BoardView::new(&board);
+        let heights = column_heights(&view);
+        let heights = &heights[..view.width()];
+
+        assert_eq!(heights, [2, 3, 0, 2]);
+        assert_eq!(aggregate_height(heights), 7);
+        // under the blocks in column 1
+        assert_eq!(holes(&view, heights), 1);
+        assert_eq!(covered_cells(&view, heights), 2);
+        assert_eq!(bumpiness(heights), 1 + 3 + 2);
+    }
+
+    #[test]
+    fn transitions_count_the_walls_and_floor() {
+        let board = board_from(&[
+            ".#..",
+            "##.#",
+            "#..#",
+        ]);
+        let view = BoardView::new(&board);
+        let heights = column_heights(&view);
+        let heights = &heights[..view.width()];
+
+        // wall|#..#|wall: 2, wall|##.#|wall: 2, wall|.#..|wall: 4
+        assert_eq!(row_transitions(&view, heights), 8);
+        // only column 1 has a gap between the floor and its top
+        assert_eq!(column_transitions(&view, heights), 2);
+    }
+
+    #[test]
+    fn wells_are_measured_against_the_lower_neighbour() {
+        let board = board_from(&[
+            "#..#",
+            "#.##",
+            "#.##",
+        ]);
+        let heights = column_heights(&BoardView::new(&board));
+        let heights = &heights[..4];
+
+        // column 2 is only 2 high, so the well in column 1 is 2 deep
+        assert_eq!(well_depth(heights, 1), 2);
+        assert_eq!(well_depth(heights, 2), 0);
+        // the wall is taller than anything
+        assert_eq!(well_depth(heights, 3), 0);
+        assert_eq!(wells(heights), 1);
+        assert_eq!(max_well_depth(heights), 2);
+    }
+
+    #[test]
+    fn tetris_ready_needs_four_rows_full_apart_from_the_well() {
+        let mut rows = [
+            "#########.",
+            "#########.",
+            "#########.",
+            "##.######.",
+        ];
+        let metrics_of = |rows: &[&str]| Metrics::new(&BoardView::new(&board_from(rows)));
+
+        assert!(!metrics_of(&rows).tetris_ready);
+        rows[3] = "#########.";
+        let metrics = metrics_of(&rows);
+        assert!(metrics.tetris_ready);
+        assert_eq!(metrics.max_well_depth, 4);
+    }
+}
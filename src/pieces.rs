@@ -1,41 +1,66 @@
 use crate::coord::Coord;
-/// This describes the different orientations for the I piece.
-/// The I piece is 4 tetriminoes long, so it doesn't rotate as
-/// nicely as the other pieces. The integer values assigned 
-/// correspond to the appropriate offset values in the I_CW_OFFSETS array.
-/// The orientations are as follows:
-/// HorizontalDown
-/// [ ][ ][ ][ ]
-/// [ ][ ][ ][ ]
-/// [o][o][o][o]
-/// [ ][ ][ ][ ]
-/// VerticalLeft
-/// [ ][o][ ][ ]
-/// [ ][o][ ][ ]
-/// [ ][o][ ][ ]
-/// [ ][o][ ][ ]
-/// HorizontalUp
+use crate::game_renderer::TetriminoType;
+/// This describes the four rotation states used by the
+/// Super Rotation System (SRS). Every piece spawns in the
+/// `Spawn` state, and a clockwise rotation moves through the
+/// states in the order they are declared. The integer values
+/// assigned correspond to the appropriate rows of the kick tables
+/// and the I_CW_OFFSETS array.
+/// The orientations for the I piece are as follows:
+/// Spawn
 /// [ ][ ][ ][ ]
 /// [o][o][o][o]
 /// [ ][ ][ ][ ]
 /// [ ][ ][ ][ ]
-/// VerticalRight
+/// Right
 /// [ ][ ][o][ ]
 /// [ ][ ][o][ ]
 /// [ ][ ][o][ ]
 /// [ ][ ][o][ ]
+/// Two
+/// [ ][ ][ ][ ]
+/// [ ][ ][ ][ ]
+/// [o][o][o][o]
+/// [ ][ ][ ][ ]
+/// Left
+/// [ ][o][ ][ ]
+/// [ ][o][ ][ ]
+/// [ ][o][ ][ ]
+/// [ ][o][ ][ ]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Orientation {
-    HorizontalDown = 0,
-    VerticalLeft   = 1,
-    HorizontalUp   = 2,
-    VerticalRight  = 3,
+    Spawn = 0,
+    Right = 1,
+    Two   = 2,
+    Left  = 3,
+}
+
+impl Orientation {
+    /// The orientation reached after a clockwise rotation.
+    pub fn cw(self) -> Orientation {
+        match self {
+            Orientation::Spawn => Orientation::Right,
+            Orientation::Right => Orientation::Two,
+            Orientation::Two   => Orientation::Left,
+            Orientation::Left  => Orientation::Spawn,
+        }
+    }
+
+    /// The orientation reached after a counterclockwise rotation.
+    pub fn ccw(self) -> Orientation {
+        match self {
+            Orientation::Spawn => Orientation::Left,
+            Orientation::Right => Orientation::Spawn,
+            Orientation::Two   => Orientation::Right,
+            Orientation::Left  => Orientation::Two,
+        }
+    }
 }
 
 /// Represents the 7 pieces.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PieceType {
-    I(Orientation), 
+    I,
     O,
     J,
     L,
@@ -44,6 +69,20 @@ pub enum PieceType {
     T,
 }
 
+impl From<PieceType> for TetriminoType {
+    fn from(piece_type: PieceType) -> TetriminoType {
+        match piece_type {
+            PieceType::I => TetriminoType::I,
+            PieceType::O => TetriminoType::O,
+            PieceType::J => TetriminoType::J,
+            PieceType::L => TetriminoType::L,
+            PieceType::S => TetriminoType::S,
+            PieceType::Z => TetriminoType::Z,
+            PieceType::T => TetriminoType::T,
+        }
+    }
+}
+
 // ---------------------------------------------------------------
 //            Initial Piece coordinates
 // ---------------------------------------------------------------
@@ -126,12 +165,57 @@ const T_COORDS : [Coord; 4] = [
 // This offset changes based on the direction of rotation.
 // The counterclockwise rotation offset coordinates, are just
 // clockwise rotations of the clockwise offsets.
-// How did I discover this? I worked it out by hand.
+// The offsets come from the SRS rotation states of the I piece (see the
+// diagrams on `Orientation` and https://harddrop.com/wiki/SRS): the piece
+// turns about the center of its 4x4 box, so after rotating about the end
+// point it has to be moved back to where SRS puts that end point.
 const I_CW_OFFSETS : [Coord; 4] = [
-    Coord { x: -2, y: -1 },  // Horizontal down
-    Coord { x: -1, y:  2 },  // Vertical left
-    Coord { x:  2, y:  1 },  // Horizontal up
-    Coord { x:  1, y: -2 },  // Vertical right
+    Coord { x: -1, y: -2 },  // Spawn
+    Coord { x: -2, y:  1 },  // Right
+    Coord { x:  1, y:  2 },  // Two
+    Coord { x:  2, y: -1 },  // Left
+];
+
+// ---------------------------------------------------------------
+//            SRS wall kicks
+// ---------------------------------------------------------------
+// When a rotated piece doesn't fit, SRS tries translating it by
+// each of these offsets in order and takes the first one that fits.
+// The tables are indexed by the orientation the piece is rotating
+// *from*. The first test is always (0, 0), i.e., the plain rotation.
+// source: https://harddrop.com/wiki/SRS
+// NOTE: positive y is up, which matches the board's coordinates.
+
+const fn kick(x: i32, y: i32) -> Coord {
+    Coord { x, y }
+}
+
+const JLSTZ_CW_KICKS : [[Coord; 5]; 4] = [
+    [kick(0, 0), kick(-1, 0), kick(-1,  1), kick(0, -2), kick(-1, -2)], // Spawn -> Right
+    [kick(0, 0), kick( 1, 0), kick( 1, -1), kick(0,  2), kick( 1,  2)], // Right -> Two
+    [kick(0, 0), kick( 1, 0), kick( 1,  1), kick(0, -2), kick( 1, -2)], // Two   -> Left
+    [kick(0, 0), kick(-1, 0), kick(-1, -1), kick(0,  2), kick(-1,  2)], // Left  -> Spawn
+];
+
+const JLSTZ_CCW_KICKS : [[Coord; 5]; 4] = [
+    [kick(0, 0), kick( 1, 0), kick( 1,  1), kick(0, -2), kick( 1, -2)], // Spawn -> Left
+    [kick(0, 0), kick( 1, 0), kick( 1, -1), kick(0,  2), kick( 1,  2)], // Right -> Spawn
+    [kick(0, 0), kick(-1, 0), kick(-1,  1), kick(0, -2), kick(-1, -2)], // Two   -> Right
+    [kick(0, 0), kick(-1, 0), kick(-1, -1), kick(0,  2), kick(-1,  2)], // Left  -> Two
+];
+
+const I_CW_KICKS : [[Coord; 5]; 4] = [
+    [kick(0, 0), kick(-2, 0), kick( 1, 0), kick(-2, -1), kick( 1,  2)], // Spawn -> Right
+    [kick(0, 0), kick(-1, 0), kick( 2, 0), kick(-1,  2), kick( 2, -1)], // Right -> Two
+    [kick(0, 0), kick( 2, 0), kick(-1, 0), kick( 2,  1), kick(-1, -2)], // Two   -> Left
+    [kick(0, 0), kick( 1, 0), kick(-2, 0), kick( 1, -2), kick(-2,  1)], // Left  -> Spawn
+];
+
+const I_CCW_KICKS : [[Coord; 5]; 4] = [
+    [kick(0, 0), kick(-1, 0), kick( 2, 0), kick(-1,  2), kick( 2, -1)], // Spawn -> Left
+    [kick(0, 0), kick( 2, 0), kick(-1, 0), kick( 2,  1), kick(-1, -2)], // Right -> Spawn
+    [kick(0, 0), kick( 1, 0), kick(-2, 0), kick( 1, -2), kick(-2,  1)], // Two   -> Right
+    [kick(0, 0), kick(-2, 0), kick( 1, 0), kick(-2, -1), kick( 1,  2)], // Left  -> Two
 ];
 
 /// Takes all the coordinates for a piece and adds an offset to them.
@@ -154,6 +238,8 @@ fn make_relative(coords: &[Coord; 4]) -> [Coord; 4] {
 /// Represents a tetris piece
 pub struct Piece {
     pub piece_type: PieceType,
+    /// The current SRS rotation state of the piece
+    pub orientation: Orientation,
     /// The coordinates of the individual tetriminoes
     pub position: [Coord; 4],
 }
@@ -161,6 +247,35 @@ pub struct Piece {
 
 
 impl Piece {
+    /// Returns a piece of the given type at its spawn location and orientation.
+    pub fn spawn(piece_type: PieceType) -> Piece {
+        // PIECE_TYPES is in the same order as the PieceType variants
+        PIECE_TYPES[piece_type as usize]
+    }
+
+    /// Returns a piece of the given type at its spawn location on a board
+    /// with the given number of columns and visible rows. Pieces spawn in
+    /// the middle of the board, rounding to the left, just above the
+    /// rows the player can see.
+    pub fn spawn_on(piece_type: PieceType, width: usize, visible_height: usize) -> Piece {
+        // the spawn coordinates are for the standard 10 by 20 board
+        let offset = Coord {
+            x: (width as i32 - 10).div_euclid(2),
+            y: visible_height as i32 - 20,
+        };
+        let piece = Piece::spawn(piece_type);
+        Piece {
+            position: add_offset(&piece.position, offset),
+            .. piece
+        }
+    }
+
+    /// Returns this piece at its spawn location and orientation
+    /// on a board of the given size.
+    pub fn respawned(&self, width: usize, visible_height: usize) -> Piece {
+        Piece::spawn_on(self.piece_type, width, visible_height)
+    }
+
     /// Calculate the new location of a piece if moved left by one space
     pub fn move_left(&self) -> Piece {
         let offset = Coord { x: -1, y: 0 };
@@ -208,19 +323,13 @@ impl Piece {
               Coord {x: relative_coords[3].y, y: -relative_coords[3].x} ];
 
         match self.piece_type {
-           PieceType::I(orientation) => {
-               let offset = I_CW_OFFSETS[orientation as usize];
+           PieceType::I => {
+               let offset = I_CW_OFFSETS[self.orientation as usize];
                let new_position = add_offset(&rel_rotated_coords, offset + center_coord);
-               let new_piece_type = 
-                   match orientation {
-                       Orientation::HorizontalDown => PieceType::I(Orientation::VerticalLeft),
-                       Orientation::VerticalLeft   => PieceType::I(Orientation::HorizontalUp),
-                       Orientation::HorizontalUp   => PieceType::I(Orientation::VerticalRight),
-                       Orientation::VerticalRight  => PieceType::I(Orientation::HorizontalDown),
-                   };
 
                Piece {
-                   piece_type: new_piece_type,
+                   piece_type: PieceType::I,
+                   orientation: self.orientation.cw(),
                    position: new_position,
                }
            },
@@ -230,7 +339,7 @@ impl Piece {
            },
            _ => {
                let new_position = add_offset(&rel_rotated_coords, center_coord);
-               Piece { position: new_position, .. *self }
+               Piece { position: new_position, orientation: self.orientation.cw(), .. *self }
            }
         }
     }
@@ -253,23 +362,17 @@ impl Piece {
               Coord {x: -relative_coords[3].y, y: relative_coords[3].x} ];
 
         match self.piece_type {
-           PieceType::I(orientation) => {
-               let cw_offset = I_CW_OFFSETS[orientation as usize];
+           PieceType::I => {
+               let cw_offset = I_CW_OFFSETS[self.orientation as usize];
                // the offset when rotating counter clockwise, happens to the be 90 degree clockwise
                // rotation of the clockwise offset. If you want to prove it to yourself, just draw
                // it out.
                let offset = Coord { x: cw_offset.y, y: -cw_offset.x };
                let new_position = add_offset(&rel_rotated_coords, center_coord + offset);
-               let new_piece_type = 
-                   match orientation {
-                       Orientation::HorizontalDown => PieceType::I(Orientation::VerticalRight),
-                       Orientation::VerticalLeft   => PieceType::I(Orientation::HorizontalDown),
-                       Orientation::HorizontalUp   => PieceType::I(Orientation::VerticalLeft),
-                       Orientation::VerticalRight  => PieceType::I(Orientation::HorizontalUp),
-                   };
 
                Piece {
-                   piece_type: new_piece_type,
+                   piece_type: PieceType::I,
+                   orientation: self.orientation.ccw(),
                    position: new_position,
                }
            },
@@ -279,27 +382,71 @@ impl Piece {
            },
            _ => {
                let new_position = add_offset(&rel_rotated_coords, center_coord);
-               Piece { position: new_position, .. *self }
+               Piece { position: new_position, orientation: self.orientation.ccw(), .. *self }
            }
         }
 
     }
+
+    /// Returns the clockwise rotation of the piece translated by each of
+    /// the SRS kick offsets, in the order they should be tested.
+    pub fn cw_rot_kicks(&self) -> [Piece; 5] {
+        let kicks =
+            match self.piece_type {
+                PieceType::I => &I_CW_KICKS,
+                _            => &JLSTZ_CW_KICKS,
+            };
+
+        self.kicked(self.cw_rot(), &kicks[self.orientation as usize])
+    }
+
+    /// Returns the counterclockwise rotation of the piece translated by each
+    /// of the SRS kick offsets, in the order they should be tested.
+    pub fn ccw_rot_kicks(&self) -> [Piece; 5] {
+        let kicks =
+            match self.piece_type {
+                PieceType::I => &I_CCW_KICKS,
+                _            => &JLSTZ_CCW_KICKS,
+            };
+
+        self.kicked(self.ccw_rot(), &kicks[self.orientation as usize])
+    }
+
+    /// Applies each kick offset to a rotated piece.
+    fn kicked(&self, rotated: Piece, kicks: &[Coord; 5]) -> [Piece; 5] {
+        // the O piece doesn't rotate, so it shouldn't get kicked around either
+        if self.piece_type == PieceType::O {
+            return [rotated; 5];
+        }
+
+        kicks.map(|offset| Piece {
+            position: add_offset(&rotated.position, offset),
+            .. rotated
+        })
+    }
 }
 
 pub const PIECE_TYPES : [Piece; 7] = [
-    Piece { piece_type: PieceType::I(Orientation::HorizontalDown),
+    Piece { piece_type: PieceType::I,
+                orientation: Orientation::Spawn,
                 position: I_COORDS, },
     Piece { piece_type: PieceType::O,
+                orientation: Orientation::Spawn,
                 position: O_COORDS, },
     Piece { piece_type: PieceType::J,
+                orientation: Orientation::Spawn,
                 position: J_COORDS, },
     Piece { piece_type: PieceType::L,
+                orientation: Orientation::Spawn,
                 position: L_COORDS, },
     Piece { piece_type: PieceType::S,
+                orientation: Orientation::Spawn,
                 position: S_COORDS, },
     Piece { piece_type: PieceType::Z,
+                orientation: Orientation::Spawn,
                 position: Z_COORDS, },
     Piece { piece_type: PieceType::T,
+                orientation: Orientation::Spawn,
                 position: T_COORDS, },
 ];
 
@@ -307,6 +454,31 @@ pub const PIECE_TYPES : [Piece; 7] = [
 mod tests {
     use super::*;
 
+    #[test]
+    fn respawned_pieces_are_at_their_spawn_location() {
+        for piece in PIECE_TYPES.iter() {
+            let moved = piece.cw_rot().move_left().apply_gravity(5);
+
+            assert_eq!(moved.respawned(10, 20), *piece);
+        }
+    }
+
+    #[test]
+    fn pieces_spawn_in_the_middle_of_any_board() {
+        // the I piece is centered, the others round to the left
+        let narrow = Piece::spawn_on(PieceType::I, 4, 20);
+        assert!(narrow.position.iter().map(|c| c.x).eq([3, 2, 1, 0]));
+        let wide = Piece::spawn_on(PieceType::T, 20, 20);
+        assert!(wide.position.iter().all(|c| (8..=10).contains(&c.x)));
+        // odd widths round to the left as well
+        let odd = Piece::spawn_on(PieceType::I, 9, 20);
+        assert!(odd.position.iter().all(|c| (2..=5).contains(&c.x)));
+
+        // and always just above the visible rows
+        let tall = Piece::spawn_on(PieceType::O, 10, 40);
+        assert!(tall.position.iter().all(|c| c.y == 40 || c.y == 41));
+    }
+
     #[test]
     fn relative_coordinate_test() {
         let coords = [
@@ -447,31 +619,32 @@ mod tests {
     //         I PIECE ROTATIONS
     // ------------------------------------
     #[test]
-    // Test rotating from HorizontalDown to VerticalLeft
-    // [22]| [ ][ ][ ][ ]      [ ][a][ ][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][b][ ][ ]
-    // [20]| [a][b][c][d]  -/  [ ][c][ ][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][d][ ][ ]
+    // Test rotating from Spawn to Right
+    // [21]| [ ][ ][ ][ ]      [ ][ ][a][ ]
+    // [20]| [a][b][c][d]  -\  [ ][ ][b][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][ ][c][ ]
+    // [18]| [ ][ ][ ][ ]      [ ][ ][d][ ]
     //     +-------------------------------
     //       [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_cw_rot_horizdown_to_vertleft() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_cw_rot_spawn_to_right() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::VerticalLeft),
+                piece_type: PieceType::I,
+                orientation: Orientation::Right,
                 position: [
-                    Coord { x: 4, y: 19 }, // d
-                    Coord { x: 4, y: 20 }, // c
-                    Coord { x: 4, y: 21 }, // b
-                    Coord { x: 4, y: 22 }, // a
+                    Coord { x: 5, y: 18 }, // d
+                    Coord { x: 5, y: 19 }, // c
+                    Coord { x: 5, y: 20 }, // b
+                    Coord { x: 5, y: 21 }, // a
                 ],
             };
 
@@ -479,31 +652,32 @@ mod tests {
     }
 
     #[test]
-    // Test rotating from HorizontalDown to HorizUp
-    // [22]| [ ][ ][ ][ ]      [ ][a][ ][ ]      [ ][ ][ ][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][b][ ][ ]  -\  [d][c][b][a]
-    // [20]| [a][b][c][d]  -/  [ ][c][ ][ ]  -/  [ ][ ][ ][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][d][ ][ ]      [ ][ ][ ][ ]
+    // Test rotating from Spawn to Two
+    // [21]| [ ][ ][ ][ ]      [ ][ ][a][ ]      [ ][ ][ ][ ]
+    // [20]| [a][b][c][d]  -\  [ ][ ][b][ ]  -\  [ ][ ][ ][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][ ][c][ ]  -/  [d][c][b][a]
+    // [18]| [ ][ ][ ][ ]      [ ][ ][d][ ]      [ ][ ][ ][ ]
     //     +------------------------------------------------
     //       [3][4][5][6]      [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_cw_rot_horizdown_to_horizup() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_cw_rot_spawn_to_two() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::HorizontalUp),
+                piece_type: PieceType::I,
+                orientation: Orientation::Two,
                 position: [
-                    Coord { x: 3, y: 21 }, // d
-                    Coord { x: 4, y: 21 }, // c
-                    Coord { x: 5, y: 21 }, // b
-                    Coord { x: 6, y: 21 }, // a
+                    Coord { x: 3, y: 19 }, // d
+                    Coord { x: 4, y: 19 }, // c
+                    Coord { x: 5, y: 19 }, // b
+                    Coord { x: 6, y: 19 }, // a
                 ],
             };
 
@@ -511,78 +685,78 @@ mod tests {
     }
 
     #[test]
-    // Test rotating from HorizontalDown to VerticalRight
-    // [22]| [ ][ ][ ][ ]      [ ][a][ ][ ]      [ ][ ][ ][ ]      [ ][ ][d][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][b][ ][ ]  -\  [d][c][b][a]  -\  [ ][ ][c][ ]
-    // [20]| [a][b][c][d]  -/  [ ][c][ ][ ]  -/  [ ][ ][ ][ ]  -/  [ ][ ][b][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][d][ ][ ]      [ ][ ][ ][ ]      [ ][ ][a][ ]
+    // Test rotating from Spawn to Left
+    // [21]| [ ][ ][ ][ ]      [ ][ ][a][ ]      [ ][ ][ ][ ]      [ ][d][ ][ ]
+    // [20]| [a][b][c][d]  -\  [ ][ ][b][ ]  -\  [ ][ ][ ][ ]  -\  [ ][c][ ][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][ ][c][ ]  -/  [d][c][b][a]  -/  [ ][b][ ][ ]
+    // [18]| [ ][ ][ ][ ]      [ ][ ][d][ ]      [ ][ ][ ][ ]      [ ][a][ ][ ]
     //     +-------------------------------------------------------------------
     //       [3][4][5][6]      [3][4][5][6]      [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_cw_rot_horizdown_to_vertright() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_cw_rot_spawn_to_left() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::VerticalRight),
+                piece_type: PieceType::I,
+                orientation: Orientation::Left,
                 position: [
-                    Coord { x: 5, y: 22 }, // d
-                    Coord { x: 5, y: 21 }, // c
-                    Coord { x: 5, y: 20 }, // b
-                    Coord { x: 5, y: 19 }, // a
+                    Coord { x: 4, y: 21 }, // d
+                    Coord { x: 4, y: 20 }, // c
+                    Coord { x: 4, y: 19 }, // b
+                    Coord { x: 4, y: 18 }, // a
                 ],
             };
 
-        
-        
         assert_eq!(rotated, expected_result);
     }
 
     #[test]
-    fn i_piece_cw_rot_horizdown_to_horizdown() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_cw_rot_spawn_to_spawn() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         // 4 rotations should take us right back to where we started
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
-        let expected_result = PIECE_TYPES[0].clone();
+        let expected_result = PIECE_TYPES[0];
 
         assert_eq!(rotated, expected_result);
     }
 
     #[test]
-    // Test rotating from HorizontalDown to VerticalRight
-    // [22]| [ ][ ][ ][ ]      [ ][ ][d][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][ ][c][ ]
-    // [20]| [a][b][c][d]  -/  [ ][ ][b][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][ ][a][ ]
+    // Test rotating from Spawn to Left
+    // [21]| [ ][ ][ ][ ]      [ ][d][ ][ ]
+    // [20]| [a][b][c][d]  -\  [ ][c][ ][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][b][ ][ ]
+    // [18]| [ ][ ][ ][ ]      [ ][a][ ][ ]
     //     +-------------------------------
     //       [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_counter_cw_rot_horizdown_to_vertleft() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_counter_cw_rot_spawn_to_left() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::VerticalRight),
+                piece_type: PieceType::I,
+                orientation: Orientation::Left,
                 position: [
-                    Coord { x: 5, y: 22 }, // d
-                    Coord { x: 5, y: 21 }, // c
-                    Coord { x: 5, y: 20 }, // b
-                    Coord { x: 5, y: 19 }, // a
+                    Coord { x: 4, y: 21 }, // d
+                    Coord { x: 4, y: 20 }, // c
+                    Coord { x: 4, y: 19 }, // b
+                    Coord { x: 4, y: 18 }, // a
                 ],
             };
 
@@ -590,31 +764,32 @@ mod tests {
     }
 
     #[test]
-    // Test rotating from HorizontalDown to HorizUp
-    // [22]| [ ][ ][ ][ ]      [ ][ ][d][ ]      [ ][ ][ ][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][ ][c][ ]  -\  [d][c][b][a]
-    // [20]| [a][b][c][d]  -/  [ ][ ][b][ ]  -/  [ ][ ][ ][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][ ][a][ ]      [ ][ ][ ][ ]
+    // Test rotating from Spawn to Two
+    // [21]| [ ][ ][ ][ ]      [ ][d][ ][ ]      [ ][ ][ ][ ]
+    // [20]| [a][b][c][d]  -\  [ ][c][ ][ ]  -\  [ ][ ][ ][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][b][ ][ ]  -/  [d][c][b][a]
+    // [18]| [ ][ ][ ][ ]      [ ][a][ ][ ]      [ ][ ][ ][ ]
     //     +------------------------------------------------
     //       [3][4][5][6]      [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_counter_cw_rot_horizdown_to_horizup() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_counter_cw_rot_spawn_to_two() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::HorizontalUp),
+                piece_type: PieceType::I,
+                orientation: Orientation::Two,
                 position: [
-                    Coord { x: 3, y: 21 }, // d
-                    Coord { x: 4, y: 21 }, // c
-                    Coord { x: 5, y: 21 }, // b
-                    Coord { x: 6, y: 21 }, // a
+                    Coord { x: 3, y: 19 }, // d
+                    Coord { x: 4, y: 19 }, // c
+                    Coord { x: 5, y: 19 }, // b
+                    Coord { x: 6, y: 19 }, // a
                 ],
             };
 
@@ -622,46 +797,47 @@ mod tests {
     }
 
     #[test]
-    // Test rotating from HorizontalDown to VerticalLeft
-    // [22]| [ ][ ][ ][ ]      [ ][ ][d][ ]      [ ][ ][ ][ ]      [ ][a][ ][ ]
-    // [21]| [ ][ ][ ][ ]  -\  [ ][ ][c][ ]  -\  [d][c][b][a]  -\  [ ][b][ ][ ]
-    // [20]| [a][b][c][d]  -/  [ ][ ][b][ ]  -/  [ ][ ][ ][ ]  -/  [ ][c][ ][ ]
-    // [19]| [ ][ ][ ][ ]      [ ][ ][a][ ]      [ ][ ][ ][ ]      [ ][d][ ][ ]
+    // Test rotating from Spawn to Right
+    // [21]| [ ][ ][ ][ ]      [ ][d][ ][ ]      [ ][ ][ ][ ]      [ ][ ][a][ ]
+    // [20]| [a][b][c][d]  -\  [ ][c][ ][ ]  -\  [ ][ ][ ][ ]  -\  [ ][ ][b][ ]
+    // [19]| [ ][ ][ ][ ]  -/  [ ][b][ ][ ]  -/  [d][c][b][a]  -/  [ ][ ][c][ ]
+    // [18]| [ ][ ][ ][ ]      [ ][a][ ][ ]      [ ][ ][ ][ ]      [ ][ ][d][ ]
     //     +-------------------------------------------------------------------
     //       [3][4][5][6]      [3][4][5][6]      [3][4][5][6]      [3][4][5][6]
     // a = index 3
     // b = index 2
     // c = index 1
     // d = index 0
-    fn i_piece_counter_cw_rot_horizdown_to_vertright() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_counter_cw_rot_spawn_to_right() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
-                piece_type: PieceType::I(Orientation::VerticalLeft),
+                piece_type: PieceType::I,
+                orientation: Orientation::Right,
                 position: [
-                    Coord { x: 4, y: 19 }, // d
-                    Coord { x: 4, y: 20 }, // c
-                    Coord { x: 4, y: 21 }, // b
-                    Coord { x: 4, y: 22 }, // a
+                    Coord { x: 5, y: 18 }, // d
+                    Coord { x: 5, y: 19 }, // c
+                    Coord { x: 5, y: 20 }, // b
+                    Coord { x: 5, y: 21 }, // a
                 ],
             };
-        
+
         assert_eq!(rotated, expected_result);
     }
 
     #[test]
-    fn i_piece_counter_cw_rot_horizdown_to_horizdown() {
-        // get the I piece in the HorizontalDown orientation
-        let piece = PIECE_TYPES[0].clone();
+    fn i_piece_counter_cw_rot_spawn_to_spawn() {
+        // get the I piece in the Spawn orientation
+        let piece = PIECE_TYPES[0];
 
         // 4 rotations should take us right back to where we started
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
-        let expected_result = PIECE_TYPES[0].clone();
+        let expected_result = PIECE_TYPES[0];
 
         assert_eq!(rotated, expected_result);
     }
@@ -671,22 +847,22 @@ mod tests {
     // ------------------------------------
     #[test]
     fn o_piece_cw_rot_doesnt_change() {
-        let piece = PIECE_TYPES[1].clone();
+        let piece = PIECE_TYPES[1];
 
         let rotated = piece.cw_rot();
 
-        let expected_result = PIECE_TYPES[1].clone();
+        let expected_result = PIECE_TYPES[1];
 
         assert_eq!(rotated, expected_result);
     }
 
     #[test]
     fn o_piece_ccw_rot_doesnt_change() {
-        let piece = PIECE_TYPES[1].clone();
+        let piece = PIECE_TYPES[1];
 
         let rotated = piece.ccw_rot();
 
-        let expected_result = PIECE_TYPES[1].clone();
+        let expected_result = PIECE_TYPES[1];
 
         assert_eq!(rotated, expected_result);
     }
@@ -706,13 +882,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_single_cw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -736,13 +913,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_2_cw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -765,13 +943,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_3_cw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -785,7 +964,7 @@ mod tests {
 
     #[test]
     fn j_piece_4_cw_rot_no_change() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
@@ -805,13 +984,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_single_ccw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -835,13 +1015,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_2_ccw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -865,13 +1046,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn j_piece_3_ccw_rot() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::J,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -885,7 +1067,7 @@ mod tests {
 
     #[test]
     fn j_piece_4_ccw_rot_no_change() {
-        let piece = PIECE_TYPES[2].clone();
+        let piece = PIECE_TYPES[2];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
@@ -909,13 +1091,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_single_cw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -940,13 +1123,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_2_cw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -971,13 +1155,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_3_cw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -992,11 +1177,11 @@ mod tests {
 
     #[test]
     fn l_piece_4_cw_rot_no_change() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
-        let expected_result = PIECE_TYPES[3].clone();
+        let expected_result = PIECE_TYPES[3];
 
         assert_eq!(rotated, expected_result);
     }
@@ -1012,13 +1197,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_single_ccw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -1043,13 +1229,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_2_ccw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -1074,13 +1261,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn l_piece_3_ccw_rot() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::L,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -1095,11 +1283,11 @@ mod tests {
 
     #[test]
     fn l_piece_4_ccw_rot_no_change() {
-        let piece = PIECE_TYPES[3].clone();
+        let piece = PIECE_TYPES[3];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
-        let expected_result = PIECE_TYPES[3].clone();
+        let expected_result = PIECE_TYPES[3];
 
         assert_eq!(rotated, expected_result);
     }
@@ -1119,13 +1307,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_single_cw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -1148,13 +1337,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_2_cw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -1177,13 +1367,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_3_cw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -1196,11 +1387,11 @@ mod tests {
 
     #[test]
     fn s_piece_4_cw_rot_no_change() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
-        let expected_result = PIECE_TYPES[4].clone();
+        let expected_result = PIECE_TYPES[4];
 
         assert_eq!(rotated, expected_result);
     }
@@ -1217,13 +1408,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_single_ccw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -1246,13 +1438,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_2_ccw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -1275,13 +1468,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn s_piece_3_ccw_rot() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::S,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -1294,11 +1488,11 @@ mod tests {
 
     #[test]
     fn s_piece_4_ccw_rot_no_change() {
-        let piece = PIECE_TYPES[4].clone();
+        let piece = PIECE_TYPES[4];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
-        let expected_result = PIECE_TYPES[4].clone();
+        let expected_result = PIECE_TYPES[4];
 
         assert_eq!(rotated, expected_result);
     }
@@ -1318,13 +1512,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_single_cw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 21 }, // b
@@ -1347,13 +1542,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_2_cw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 19 }, // b
@@ -1376,13 +1572,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_3_cw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 3, y: 19 }, // b
@@ -1395,7 +1592,7 @@ mod tests {
 
     #[test]
     fn z_piece_4_cw_rot_no_change() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
@@ -1415,13 +1612,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_single_ccw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 3, y: 19 }, // b
@@ -1444,13 +1642,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_2_ccw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 19 }, // b
@@ -1473,13 +1672,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn z_piece_3_ccw_rot() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::Z,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 21 }, // b
@@ -1492,7 +1692,7 @@ mod tests {
 
     #[test]
     fn z_piece_4_ccw_rot_no_change() {
-        let piece = PIECE_TYPES[5].clone();
+        let piece = PIECE_TYPES[5];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
@@ -1515,13 +1715,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_single_cw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -1544,13 +1745,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_2_cw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -1573,13 +1775,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_3_cw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -1592,11 +1795,11 @@ mod tests {
 
     #[test]
     fn t_piece_4_cw_rot_no_change() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.cw_rot().cw_rot().cw_rot().cw_rot();
 
-        let expected_result = PIECE_TYPES[6].clone();
+        let expected_result = PIECE_TYPES[6];
 
         assert_eq!(rotated, expected_result);
     }
@@ -1612,13 +1815,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_single_ccw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Left,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 19 }, // b
@@ -1641,13 +1845,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_2_ccw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Two,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 5, y: 20 }, // b
@@ -1670,13 +1875,14 @@ mod tests {
     // c = index 2
     // d = index 3
     fn t_piece_3_ccw_rot() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot();
 
         let expected_result =
             Piece {
                 piece_type: PieceType::T,
+                orientation: Orientation::Right,
                 position: [
                     Coord { x: 4, y: 20 }, // a
                     Coord { x: 4, y: 21 }, // b
@@ -1689,12 +1895,60 @@ mod tests {
 
     #[test]
     fn t_piece_4_ccw_rot_no_change() {
-        let piece = PIECE_TYPES[6].clone();
+        let piece = PIECE_TYPES[6];
 
         let rotated = piece.ccw_rot().ccw_rot().ccw_rot().ccw_rot();
 
-        let expected_result = PIECE_TYPES[6].clone();
+        let expected_result = PIECE_TYPES[6];
 
         assert_eq!(rotated, expected_result);
     }
+
+    // ------------------------------------
+    //             SRS KICKS
+    // ------------------------------------
+    #[test]
+    fn first_kick_is_the_plain_rotation() {
+        for piece in PIECE_TYPES.iter() {
+            assert_eq!(piece.cw_rot_kicks()[0], piece.cw_rot());
+            assert_eq!(piece.ccw_rot_kicks()[0], piece.ccw_rot());
+        }
+    }
+
+    #[test]
+    fn t_piece_spawn_to_right_kicks() {
+        let piece = PIECE_TYPES[6];
+        let rotated = piece.cw_rot();
+
+        let kicks = piece.cw_rot_kicks();
+
+        // the kicks for Spawn -> Right are (0,0), (-1,0), (-1,1), (0,-2), (-1,-2)
+        assert_eq!(kicks[1].position, add_offset(&rotated.position, Coord { x: -1, y:  0 }));
+        assert_eq!(kicks[2].position, add_offset(&rotated.position, Coord { x: -1, y:  1 }));
+        assert_eq!(kicks[3].position, add_offset(&rotated.position, Coord { x:  0, y: -2 }));
+        assert_eq!(kicks[4].position, add_offset(&rotated.position, Coord { x: -1, y: -2 }));
+        assert!(kicks.iter().all(|k| k.orientation == Orientation::Right));
+    }
+
+    #[test]
+    fn i_piece_uses_its_own_kick_table() {
+        let piece = PIECE_TYPES[0].cw_rot();
+        let rotated = piece.ccw_rot();
+
+        let kicks = piece.ccw_rot_kicks();
+
+        // the kicks for I Right -> Spawn are (0,0), (2,0), (-1,0), (2,1), (-1,-2)
+        assert_eq!(kicks[1].position, add_offset(&rotated.position, Coord { x:  2, y:  0 }));
+        assert_eq!(kicks[2].position, add_offset(&rotated.position, Coord { x: -1, y:  0 }));
+        assert_eq!(kicks[3].position, add_offset(&rotated.position, Coord { x:  2, y:  1 }));
+        assert_eq!(kicks[4].position, add_offset(&rotated.position, Coord { x: -1, y: -2 }));
+    }
+
+    #[test]
+    fn o_piece_is_never_kicked() {
+        let piece = PIECE_TYPES[1];
+
+        assert!(piece.cw_rot_kicks().iter().all(|&k| k == piece));
+        assert!(piece.ccw_rot_kicks().iter().all(|&k| k == piece));
+    }
 }
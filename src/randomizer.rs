@@ -0,0 +1,528 @@
+use crate::rng::Rng;
+pub use crate::pieces::PieceType;
+
+/// The seven piece types, in a fixed order.
+pub const ALL_PIECE_TYPES : [PieceType; 7] = [
+    PieceType::I,
+    PieceType::O,
+    PieceType::J,
+    PieceType::L,
+    PieceType::S,
+    PieceType::Z,
+    PieceType::T,
+];
+
+/// Decides which piece comes next.
+pub trait Randomizer {
+    fn next_piece(&mut self) -> PieceType;
+}
+
+/// Shuffles a slice so that every permutation is equally likely.
+pub fn shuffle<T, R: Rng>(items: &mut [T], rng: &mut R) {
+    // Fisher-Yates: pick the last item from everything that's left,
+    // then the second to last, and so on.
+    for i in (1..items.len()).rev() {
+        let j = rng.next_index(i + 1);
+        items.swap(i, j);
+    }
+}
+
+/// The randomizer used by modern games: all seven pieces are put
+/// in a bag, shuffled, and dealt out before the bag is refilled.
+/// This guarantees every piece shows up once every seven pieces.
+pub struct SevenBag<R: Rng> {
+    rng: R,
+    bag: [PieceType; 7],
+    /// Index of the next piece to deal from the bag.
+    index: usize,
+}
+
+impl<R: Rng> SevenBag<R> {
+    pub fn new(rng: R) -> SevenBag<R> {
+        SevenBag {
+            rng,
+            bag: ALL_PIECE_TYPES,
+            // start with an empty bag so the first piece shuffles it
+            index: ALL_PIECE_TYPES.len(),
+        }
+    }
+}
+
+impl<R: Rng> Randomizer for SevenBag<R> {
+    fn next_piece(&mut self) -> PieceType {
+        if self.index == self.bag.len() {
+            shuffle(&mut self.bag, &mut self.rng);
+            self.index = 0;
+        }
+
+        let piece_type = self.bag[self.index];
+        self.index += 1;
+        piece_type
+    }
+}
+
+/// Like the seven bag, but with two of every piece in the bag. Pieces can
+/// repeat within a bag, so streaks and droughts are a bit longer.
+pub struct FourteenBag<R: Rng> {
+    rng: R,
+    bag: [PieceType; 14],
+    /// Index of the next piece to deal from the bag.
+    index: usize,
+}
+
+impl<R: Rng> FourteenBag<R> {
+    pub fn new(rng: R) -> FourteenBag<R> {
+        let mut bag = [PieceType::I; 14];
+        bag[..7].copy_from_slice(&ALL_PIECE_TYPES);
+        bag[7..].copy_from_slice(&ALL_PIECE_TYPES);
+        FourteenBag {
+            rng,
+            bag,
+            index: bag.len(),
+        }
+    }
+}
+
+impl<R: Rng> Randomizer for FourteenBag<R> {
+    fn next_piece(&mut self) -> PieceType {
+        if self.index == self.bag.len() {
+            shuffle(&mut self.bag, &mut self.rng);
+            self.index = 0;
+        }
+
+        let piece_type = self.bag[self.index];
+        self.index += 1;
+        piece_type
+    }
+}
+
+/// Picks every piece at random, without looking at what came before.
+pub struct PureRandom<R: Rng> {
+    rng: R,
+}
+
+impl<R: Rng> PureRandom<R> {
+    pub fn new(rng: R) -> PureRandom<R> {
+        PureRandom { rng }
+    }
+}
+
+impl<R: Rng> Randomizer for PureRandom<R> {
+    fn next_piece(&mut self) -> PieceType {
+        ALL_PIECE_TYPES[self.rng.next_index(ALL_PIECE_TYPES.len())]
+    }
+}
+
+/// The randomizer from the NES game. It rolls one of eight values, and if
+/// that's the extra eighth value or the same piece as last time it rolls
+/// again once, this time out of the seven pieces. Whatever comes up
+/// the second time is kept, even if it's a repeat.
+pub struct Nes<R: Rng> {
+    rng: R,
+    previous: Option<PieceType>,
+}
+
+impl<R: Rng> Nes<R> {
+    pub fn new(rng: R) -> Nes<R> {
+        Nes { rng, previous: None }
+    }
+}
+
+impl<R: Rng> Randomizer for Nes<R> {
+    fn next_piece(&mut self) -> PieceType {
+        let roll = self.rng.next_index(ALL_PIECE_TYPES.len() + 1);
+        let piece_type = match ALL_PIECE_TYPES.get(roll) {
+            Some(&piece_type) if Some(piece_type) != self.previous => piece_type,
+            _ => ALL_PIECE_TYPES[self.rng.next_index(ALL_PIECE_TYPES.len())],
+        };
+
+        self.previous = Some(piece_type);
+        piece_type
+    }
+}
+
+/// The pieces that can start a TGM game. S, Z and O are left out
+/// because they can't be placed without making a hole.
+const TGM_FIRST_PIECES : [PieceType; 4] = [
+    PieceType::I,
+    PieceType::J,
+    PieceType::L,
+    PieceType::T,
+];
+
+/// Picks the first piece of a TGM game.
+fn tgm_first_piece<R: Rng>(rng: &mut R) -> PieceType {
+    TGM_FIRST_PIECES[rng.next_index(TGM_FIRST_PIECES.len())]
+}
+
+/// Moves a piece into the history, pushing the oldest piece out.
+fn push_history(history: &mut [PieceType; 4], piece_type: PieceType) {
+    history.copy_within(1.., 0);
+    history[3] = piece_type;
+}
+
+/// The randomizer from the first two TGM games. It remembers the last
+/// four pieces, and rerolls a few times to try to get a piece that
+/// isn't one of them.
+pub struct Tgm<R: Rng> {
+    rng: R,
+    history: [PieceType; 4],
+    /// The number of tries to find a piece that isn't in the history.
+    rolls: u32,
+    first: bool,
+}
+
+impl<R: Rng> Tgm<R> {
+    /// The randomizer from TGM1, with 4 rolls.
+    pub fn tgm1(rng: R) -> Tgm<R> {
+        Tgm {
+            rng,
+            history: [PieceType::Z; 4],
+            rolls: 4,
+            first: true,
+        }
+    }
+
+    /// The randomizer from TGM2, with 6 rolls.
+    pub fn tgm2(rng: R) -> Tgm<R> {
+        Tgm {
+            rng,
+            history: [PieceType::Z, PieceType::S, PieceType::S, PieceType::Z],
+            rolls: 6,
+            first: true,
+        }
+    }
+}
+
+impl<R: Rng> Randomizer for Tgm<R> {
+    fn next_piece(&mut self) -> PieceType {
+        let piece_type = if self.first {
+            self.first = false;
+            tgm_first_piece(&mut self.rng)
+        } else {
+            let mut piece_type = PieceType::I;
+            for _ in 0..self.rolls {
+                piece_type = ALL_PIECE_TYPES[self.rng.next_index(ALL_PIECE_TYPES.len())];
+                if !self.history.contains(&piece_type) {
+                    break;
+                }
+            }
+            piece_type
+        };
+
+        push_history(&mut self.history, piece_type);
+        piece_type
+    }
+}
+
+/// The randomizer from TGM3. Pieces are drawn from a pool of 35, which
+/// starts with 5 of each piece. Every piece drawn is replaced in the pool
+/// by the piece that hasn't been seen for the longest, so pieces that
+/// are in a drought become more and more likely.
+pub struct Tgm3<R: Rng> {
+    rng: R,
+    history: [PieceType; 4],
+    pool: [PieceType; 35],
+    /// The pieces that have been dealt, least recently seen first.
+    order: [PieceType; 7],
+    /// The number of different pieces that have been dealt so far.
+    order_len: usize,
+    first: bool,
+}
+
+/// The number of tries TGM3 makes to find a piece that isn't in the history.
+const TGM3_ROLLS : usize = 6;
+
+impl<R: Rng> Tgm3<R> {
+    pub fn new(rng: R) -> Tgm3<R> {
+        let mut pool = [PieceType::I; 35];
+        for (i, piece_type) in pool.iter_mut().enumerate() {
+            *piece_type = ALL_PIECE_TYPES[i / 5];
+        }
+
+        Tgm3 {
+            rng,
+            history: [PieceType::S, PieceType::Z, PieceType::S, PieceType::Z],
+            pool,
+            order: ALL_PIECE_TYPES,
+            order_len: 0,
+            first: true,
+        }
+    }
+
+    /// Moves a piece to the back of the drought order.
+    fn saw(&mut self, piece_type: PieceType) {
+        if let Some(i) = self.order[..self.order_len].iter().position(|&p| p == piece_type) {
+            self.order.copy_within(i + 1..self.order_len, i);
+            self.order_len -= 1;
+        }
+        self.order[self.order_len] = piece_type;
+        self.order_len += 1;
+    }
+}
+
+impl<R: Rng> Randomizer for Tgm3<R> {
+    fn next_piece(&mut self) -> PieceType {
+        if self.first {
+            self.first = false;
+            let piece_type = tgm_first_piece(&mut self.rng);
+            push_history(&mut self.history, piece_type);
+            return piece_type;
+        }
+
+        let mut index = 0;
+        let mut piece_type = PieceType::I;
+        for roll in 0..TGM3_ROLLS {
+            index = self.rng.next_index(self.pool.len());
+            piece_type = self.pool[index];
+            if !self.history.contains(&piece_type) || roll == TGM3_ROLLS - 1 {
+                break;
+            }
+            // a rejected piece still gets swapped for the droughted piece
+            if self.order_len > 0 {
+                self.pool[index] = self.order[0];
+            }
+        }
+
+        self.saw(piece_type);
+        self.pool[index] = self.order[0];
+        push_history(&mut self.history, piece_type);
+        piece_type
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The randomizers that come with the crate, for picking one at runtime.
+pub enum RandomizerKind {
+    SevenBag,
+    FourteenBag,
+    PureRandom,
+    Nes,
+    Tgm1,
+    Tgm2,
+    Tgm3,
+}
+
+/// Any of the randomizers that come with the crate.
+/// Useful when the randomizer is chosen by the player.
+pub enum AnyRandomizer<R: Rng> {
+    SevenBag(SevenBag<R>),
+    FourteenBag(FourteenBag<R>),
+    PureRandom(PureRandom<R>),
+    Nes(Nes<R>),
+    Tgm(Tgm<R>),
+    Tgm3(Tgm3<R>),
+}
+
+impl<R: Rng> AnyRandomizer<R> {
+    pub fn new(kind: RandomizerKind, rng: R) -> AnyRandomizer<R> {
+        match kind {
+            RandomizerKind::SevenBag    => AnyRandomizer::SevenBag(SevenBag::new(rng)),
+            RandomizerKind::FourteenBag => AnyRandomizer::FourteenBag(FourteenBag::new(rng)),
+            RandomizerKind::PureRandom  => AnyRandomizer::PureRandom(PureRandom::new(rng)),
+            RandomizerKind::Nes         => AnyRandomizer::Nes(Nes::new(rng)),
+            RandomizerKind::Tgm1        => AnyRandomizer::Tgm(Tgm::tgm1(rng)),
+            RandomizerKind::Tgm2        => AnyRandomizer::Tgm(Tgm::tgm2(rng)),
+            RandomizerKind::Tgm3        => AnyRandomizer::Tgm3(Tgm3::new(rng)),
+        }
+    }
+}
+
+impl<R: Rng> Randomizer for AnyRandomizer<R> {
+    fn next_piece(&mut self) -> PieceType {
+        match self {
+            AnyRandomizer::SevenBag(r)    => r.next_piece(),
+            AnyRandomizer::FourteenBag(r) => r.next_piece(),
+            AnyRandomizer::PureRandom(r)  => r.next_piece(),
+            AnyRandomizer::Nes(r)         => r.next_piece(),
+            AnyRandomizer::Tgm(r)         => r.next_piece(),
+            AnyRandomizer::Tgm3(r)        => r.next_piece(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rng whose bounded numbers are decided by the test
+    struct Choices {
+        choices: [u32; 6],
+        i: usize,
+    }
+
+    impl Rng for Choices {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!()
+        }
+
+        fn next_bounded(&mut self, bound: u32) -> u32 {
+            let r = self.choices[self.i];
+            assert!(r < bound);
+            self.i += 1;
+            r
+        }
+    }
+
+    /// A simple linear congruential generator
+    struct Lcg(u32);
+
+    impl Rng for Lcg {
+        fn next_u32(&mut self) -> u32 {
+            self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+            self.0
+        }
+    }
+
+    /// Rng that hands out a fixed list of bounded numbers, over and over
+    struct Cycle {
+        values: &'static [u32],
+        i: usize,
+    }
+
+    impl Cycle {
+        fn new(values: &'static [u32]) -> Cycle {
+            Cycle { values, i: 0 }
+        }
+    }
+
+    impl Rng for Cycle {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!()
+        }
+
+        fn next_bounded(&mut self, bound: u32) -> u32 {
+            let r = self.values[self.i % self.values.len()];
+            assert!(r < bound);
+            self.i += 1;
+            r
+        }
+    }
+
+    #[test]
+    fn every_shuffle_is_equally_likely() {
+        // Fisher-Yates on 7 items asks for numbers in 0..7, 0..6, ..., 0..2.
+        // That's 7! possible sets of answers, and if each of them gives a
+        // different permutation then every permutation is equally likely.
+        let mut seen = [false; 5040];
+        let mut count = 0;
+
+        for a in 0..7 {
+            for b in 0..6 {
+                for c in 0..5 {
+                    for d in 0..4 {
+                        for e in 0..3 {
+                            for f in 0..2 {
+                                let mut rng = Choices { choices: [a, b, c, d, e, f], i: 0 };
+                                let mut bag = [0, 1, 2, 3, 4, 5, 6];
+                                shuffle(&mut bag, &mut rng);
+
+                                // rank the permutation with the factorial number system
+                                let mut rank = 0;
+                                for i in 0..bag.len() {
+                                    let smaller = bag[i+1..].iter().filter(|&&x| x < bag[i]).count();
+                                    rank = rank * (bag.len() - i) + smaller;
+                                }
+
+                                assert!(!seen[rank]);
+                                seen[rank] = true;
+                                count += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(count, 5040);
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn seven_bag_deals_every_piece_once_per_bag() {
+        let mut randomizer = SevenBag::new(Lcg(12345));
+
+        for _ in 0..100 {
+            let mut dealt = [false; 7];
+            for _ in 0..7 {
+                let piece_type = randomizer.next_piece();
+                assert!(!dealt[piece_type as usize]);
+                dealt[piece_type as usize] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn fourteen_bag_deals_every_piece_twice_per_bag() {
+        let mut randomizer = FourteenBag::new(Lcg(12345));
+
+        for _ in 0..100 {
+            let mut dealt = [0; 7];
+            for _ in 0..14 {
+                dealt[randomizer.next_piece() as usize] += 1;
+            }
+            assert_eq!(dealt, [2; 7]);
+        }
+    }
+
+    #[test]
+    fn nes_rerolls_repeats_once() {
+        // I, then I again which gets rerolled to O, then the eighth
+        // value which gets rerolled to O, a repeat that is kept
+        let mut randomizer = Nes::new(Cycle::new(&[0, 0, 1, 7, 1]));
+
+        assert_eq!(randomizer.next_piece(), PieceType::I);
+        assert_eq!(randomizer.next_piece(), PieceType::O);
+        assert_eq!(randomizer.next_piece(), PieceType::O);
+        assert_eq!(randomizer.rng.i, 5);
+    }
+
+    #[test]
+    fn tgm_never_starts_with_s_z_or_o() {
+        for seed in 0..100 {
+            for kind in [RandomizerKind::Tgm1, RandomizerKind::Tgm2, RandomizerKind::Tgm3] {
+                let mut randomizer = AnyRandomizer::new(kind, Lcg(seed));
+                assert!(TGM_FIRST_PIECES.contains(&randomizer.next_piece()));
+            }
+        }
+    }
+
+    #[test]
+    fn tgm_rerolls_pieces_in_the_history() {
+        // start with T, then roll Z (in the starting history), T and L
+        let mut randomizer = Tgm::tgm1(Cycle::new(&[3, 5, 6, 3]));
+
+        assert_eq!(randomizer.next_piece(), PieceType::T);
+        assert_eq!(randomizer.next_piece(), PieceType::L);
+        assert_eq!(randomizer.rng.i, 4);
+
+        // keeps the last roll once it runs out of rerolls
+        let mut randomizer = Tgm::tgm1(Cycle::new(&[0, 5, 0, 5, 5]));
+        randomizer.next_piece();
+        assert_eq!(randomizer.next_piece(), PieceType::Z);
+        assert_eq!(randomizer.rng.i, 5);
+    }
+
+    #[test]
+    fn tgm3_replaces_dealt_pieces_with_the_droughted_piece() {
+        let mut randomizer = Tgm3::new(Lcg(99));
+        // the first piece is picked separately and doesn't touch the pool
+        randomizer.next_piece();
+
+        for _ in 0..1000 {
+            let piece_type = randomizer.next_piece();
+            // the piece just dealt is the most recently seen,
+            // and the least recently seen one has been added to the pool
+            assert_eq!(randomizer.order[randomizer.order_len - 1], piece_type);
+            assert!(randomizer.pool.contains(&randomizer.order[0]));
+        }
+        assert_eq!(randomizer.order_len, 7);
+
+        // every piece shows up once in the order
+        let mut seen = [false; 7];
+        for piece_type in randomizer.order {
+            seen[piece_type as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
@@ -1,7 +1,146 @@
-/// This trait is intended to be used to
-/// permutate the order of the pieces in
-/// the game after the current permutation
-/// has been used.
+/// A source of random numbers, used by the randomizers to
+/// decide the order of the pieces.
 pub trait Rng {
-    fn next(&mut self) -> usize;
+    /// Returns the next random number. Every `u32` value
+    /// should be equally likely.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns a random number in the range `0..bound`, with every
+    /// value equally likely. `bound` must not be zero.
+    fn next_bounded(&mut self, bound: u32) -> u32 {
+        // Taking the remainder of next_u32() would favor the smaller values
+        // whenever bound doesn't divide 2^32 evenly. Throw away the first
+        // (2^32 % bound) values so the ones that are left divide evenly.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+
+    /// Returns a random index into a slice of length `len`.
+    fn next_index(&mut self, len: usize) -> usize {
+        self.next_bounded(len as u32) as usize
+    }
+}
+
+/// A small, fast generator from the PCG family (PCG32, XSH RR variant).
+/// The same seed always gives the same sequence, on every platform,
+/// so seeds can be shared to replay a game or race on the same pieces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pcg32 {
+    state: u64,
+    /// Selects one of 2^63 independent sequences. Always odd.
+    increment: u64,
+}
+
+const PCG_MULTIPLIER : u64 = 6364136223846793005;
+const PCG_DEFAULT_STREAM : u64 = 0xda3e39cb94b95bdb;
+
+impl Pcg32 {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Pcg32 {
+        Pcg32::with_stream(seed, PCG_DEFAULT_STREAM)
+    }
+
+    /// Creates a generator from a seed and a stream. Generators with the same
+    /// seed but different streams give unrelated sequences.
+    pub fn with_stream(seed: u64, stream: u64) -> Pcg32 {
+        let mut rng = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.increment);
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.step();
+        // permute the old state so the low bits are as random as the high ones
+        let xor_shifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation = (old_state >> 59) as u32;
+        xor_shifted.rotate_right(rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rng that returns a fixed sequence of numbers
+    struct Scripted {
+        values: [u32; 4],
+        i: usize,
+    }
+
+    impl Rng for Scripted {
+        fn next_u32(&mut self) -> u32 {
+            let r = self.values[self.i];
+            self.i += 1;
+            r
+        }
+    }
+
+    #[test]
+    fn values_below_the_threshold_are_rejected() {
+        // 2^32 % 7 == 4, so 0 through 3 have to be thrown away
+        let mut rng = Scripted { values: [0, 3, 11, 0], i: 0 };
+
+        assert_eq!(rng.next_bounded(7), 4);
+        assert_eq!(rng.i, 3);
+    }
+
+    #[test]
+    fn accepted_values_divide_evenly_by_the_bound() {
+        for bound in 1..=64u64 {
+            let threshold = (bound as u32).wrapping_neg() as u64 % bound;
+            let accepted = (1u64 << 32) - threshold;
+            assert_eq!(accepted % bound, 0, "bound = {}", bound);
+        }
+    }
+
+    #[test]
+    fn largest_value_is_in_range() {
+        let mut rng = Scripted { values: [u32::MAX; 4], i: 0 };
+
+        assert!(rng.next_bounded(7) < 7);
+        assert_eq!(rng.next_bounded(1), 0);
+    }
+
+    #[test]
+    fn pcg32_matches_the_reference_implementation() {
+        // first outputs of the reference pcg32 demo, seeded with (42, 54)
+        let mut rng = Pcg32::with_stream(42, 54);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_sequence() {
+        let mut a = Pcg32::new(1234);
+        let mut b = Pcg32::new(1234);
+        let mut c = Pcg32::new(1235);
+
+        let mut differs = false;
+        for _ in 0..100 {
+            let value = a.next_u32();
+            assert_eq!(value, b.next_u32());
+            differs |= value != c.next_u32();
+        }
+        assert!(differs);
+    }
 }
@@ -0,0 +1,356 @@
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// Whether the last piece was spun into place.
+/// Only the T piece can be spun.
+pub enum TSpin {
+    #[default]
+    None,
+    /// A T-spin with only one of the corners in front of the T filled.
+    Mini,
+    Full,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Describes the lines cleared by a piece as it locked, so the renderer
+/// can announce it. T-spins that don't clear any lines are reported too.
+pub struct LineClear {
+    /// The number of lines cleared.
+    pub lines: u32,
+    pub t_spin: TSpin,
+    /// The number of line clears in a row before this one.
+    /// 0 for the first clear of a combo.
+    pub combo: u32,
+    /// true when this clear and the last one were both difficult.
+    pub back_to_back: bool,
+    /// true when the clear left the board completely empty.
+    pub perfect_clear: bool,
+    /// The points awarded for the clear, including any bonuses.
+    pub points: u32,
+}
+
+impl LineClear {
+    /// Returns `true` for clears that keep a back-to-back chain going:
+    /// tetrises and T-spins that clear lines.
+    pub fn is_difficult(&self) -> bool {
+        self.lines == 4 || (self.t_spin != TSpin::None && self.lines > 0)
+    }
+
+    /// Returns the name of the clear, e.g. "T-SPIN DOUBLE".
+    pub fn name(&self) -> &'static str {
+        match (self.t_spin, self.lines) {
+            (TSpin::None, 1) => "SINGLE",
+            (TSpin::None, 2) => "DOUBLE",
+            (TSpin::None, 3) => "TRIPLE",
+            (TSpin::None, 4) => "TETRIS",
+            (TSpin::Mini, 0) => "T-SPIN MINI",
+            (TSpin::Mini, 1) => "T-SPIN MINI SINGLE",
+            (TSpin::Mini, 2) => "T-SPIN MINI DOUBLE",
+            (TSpin::Full, 0) => "T-SPIN",
+            (TSpin::Full, 1) => "T-SPIN SINGLE",
+            (TSpin::Full, 2) => "T-SPIN DOUBLE",
+            (TSpin::Full, 3) => "T-SPIN TRIPLE",
+            _ => "",
+        }
+    }
+}
+
+/// Points awarded for each row a piece falls during a soft drop.
+pub const SOFT_DROP_POINTS : u32 = 1;
+
+/// Points awarded for each row a piece falls during a hard drop.
+pub const HARD_DROP_POINTS : u32 = 2;
+
+/// Points awarded per level for each clear in a combo after the first.
+pub const COMBO_POINTS : u32 = 50;
+
+/// Returns the points a clear is worth at level 1, before any bonuses.
+/// source: https://tetris.wiki/Scoring
+pub fn guideline_points(lines: u32, t_spin: TSpin) -> u32 {
+    match (t_spin, lines) {
+        (TSpin::None, 1) => 100,
+        (TSpin::None, 2) => 300,
+        (TSpin::None, 3) => 500,
+        (TSpin::None, 4) => 800,
+        (TSpin::Mini, 0) => 100,
+        (TSpin::Mini, 1) => 200,
+        (TSpin::Mini, 2) => 400,
+        (TSpin::Full, 0) => 400,
+        (TSpin::Full, 1) => 800,
+        (TSpin::Full, 2) => 1200,
+        (TSpin::Full, 3) => 1600,
+        _ => 0,
+    }
+}
+
+/// Returns the bonus a perfect clear is worth at level 1. This is awarded
+/// on top of the points for the line clear itself.
+pub fn perfect_clear_points(lines: u32, back_to_back: bool) -> u32 {
+    match lines {
+        1 => 800,
+        2 => 1200,
+        3 => 1800,
+        4 if back_to_back => 3200,
+        4 => 2000,
+        _ => 0,
+    }
+}
+
+/// Decides how many points each action is worth and when the game
+/// levels up. Implement this to play by a different set of rules.
+pub trait ScoringRules {
+    /// The level a new game starts on.
+    fn start_level(&self) -> usize {
+        1
+    }
+
+    /// Returns the points a line clear or T-spin is worth at the given level,
+    /// including any bonuses. The `points` of the clear haven't been filled in yet.
+    fn line_clear_points(&self, line_clear: &LineClear, level: usize) -> u32;
+
+    /// Returns the points for soft dropping a piece by some number of rows.
+    fn soft_drop_points(&self, rows: u32) -> u32;
+
+    /// Returns the points for hard dropping a piece by some number of rows.
+    fn hard_drop_points(&self, rows: u32) -> u32;
+
+    /// Called every time a piece locks, with the line clear or T-spin it made,
+    /// if any. Returns the level the game should be on from now on.
+    fn level_after_lock(&mut self, line_clear: Option<&LineClear>, level: usize) -> usize;
+}
+
+/// Returns the points a clear is worth under the guideline: points scale
+/// with the level, back-to-back difficult clears are worth half as much
+/// again, every clear in a combo after the first adds a bonus and so does
+/// emptying the board.
+pub fn guideline_line_clear_points(line_clear: &LineClear, level: usize) -> u32 {
+    let level = level as u32;
+    let mut points = guideline_points(line_clear.lines, line_clear.t_spin) * level;
+    if line_clear.back_to_back {
+        points += points / 2;
+    }
+    points += COMBO_POINTS * line_clear.combo * level;
+    if line_clear.perfect_clear {
+        points += perfect_clear_points(line_clear.lines, line_clear.back_to_back) * level;
+    }
+    points
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// The rules fourtris has always used for leveling: clearing 1, 2, 3 or 4
+/// lines counts 1, 3, 5 or 8 towards the goal, and each level needs 5 more
+/// than the last. Points follow the guideline.
+pub struct Classic {
+    /// The goal earned by clearing lines.
+    line_clear_score: u32,
+    /// The goal needed to get to the next level.
+    next_level_score: u32,
+}
+
+impl Default for Classic {
+    fn default() -> Self {
+        Classic {
+            line_clear_score: 0,
+            next_level_score: 5,
+        }
+    }
+}
+
+impl ScoringRules for Classic {
+    fn line_clear_points(&self, line_clear: &LineClear, level: usize) -> u32 {
+        guideline_line_clear_points(line_clear, level)
+    }
+
+    fn soft_drop_points(&self, rows: u32) -> u32 {
+        SOFT_DROP_POINTS * rows
+    }
+
+    fn hard_drop_points(&self, rows: u32) -> u32 {
+        HARD_DROP_POINTS * rows
+    }
+
+    fn level_after_lock(&mut self, line_clear: Option<&LineClear>, level: usize) -> usize {
+        let lines = line_clear.map_or(0, |line_clear| line_clear.lines);
+        self.line_clear_score += match lines {
+            1 => 1,
+            2 => 3,
+            3 => 5,
+            4 => 8,
+            _ => 0,
+        };
+
+        if self.line_clear_score > self.next_level_score {
+            self.next_level_score += 5 * (level + 2) as u32;
+            level + 1
+        } else {
+            level
+        }
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// The NES rules: 40, 100, 300 or 1200 points for 1 to 4 lines times the
+/// level plus one, and a new level every 10 lines. There are no bonuses
+/// and no hard drop points. Levels start at 0.
+pub struct Nes {
+    lines: u32,
+}
+
+impl ScoringRules for Nes {
+    fn start_level(&self) -> usize {
+        0
+    }
+
+    fn line_clear_points(&self, line_clear: &LineClear, level: usize) -> u32 {
+        let points = match line_clear.lines {
+            1 => 40,
+            2 => 100,
+            3 => 300,
+            4 => 1200,
+            _ => 0,
+        };
+        points * (level as u32 + 1)
+    }
+
+    fn soft_drop_points(&self, rows: u32) -> u32 {
+        rows
+    }
+
+    fn hard_drop_points(&self, _rows: u32) -> u32 {
+        0
+    }
+
+    fn level_after_lock(&mut self, line_clear: Option<&LineClear>, level: usize) -> usize {
+        self.lines += line_clear.map_or(0, |line_clear| line_clear.lines);
+        core::cmp::max(level, (self.lines / 10) as usize)
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+/// The guideline rules with a variable goal: each level needs 5 times the
+/// level towards the goal, and each clear counts its base points divided
+/// by 100 towards it, e.g. 8 for a tetris. Back-to-back clears count half
+/// as much again. Any goal left over carries on to the next level.
+pub struct Guideline {
+    goal: u32,
+}
+
+impl ScoringRules for Guideline {
+    fn line_clear_points(&self, line_clear: &LineClear, level: usize) -> u32 {
+        guideline_line_clear_points(line_clear, level)
+    }
+
+    fn soft_drop_points(&self, rows: u32) -> u32 {
+        SOFT_DROP_POINTS * rows
+    }
+
+    fn hard_drop_points(&self, rows: u32) -> u32 {
+        HARD_DROP_POINTS * rows
+    }
+
+    fn level_after_lock(&mut self, line_clear: Option<&LineClear>, mut level: usize) -> usize {
+        if let Some(line_clear) = line_clear {
+            let mut goal = guideline_points(line_clear.lines, line_clear.t_spin) / 100;
+            if line_clear.back_to_back {
+                goal += goal / 2;
+            }
+            self.goal += goal;
+        }
+
+        while self.goal >= 5 * level as u32 {
+            self.goal -= 5 * level as u32;
+            level += 1;
+        }
+        level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_tetrises_and_t_spins_with_lines_are_difficult() {
+        let clear = |lines, t_spin| LineClear { lines, t_spin, combo: 0, back_to_back: false, perfect_clear: false, points: 0 };
+
+        assert!(clear(4, TSpin::None).is_difficult());
+        assert!(clear(1, TSpin::Mini).is_difficult());
+        assert!(clear(2, TSpin::Full).is_difficult());
+        assert!(!clear(3, TSpin::None).is_difficult());
+        assert!(!clear(0, TSpin::Full).is_difficult());
+    }
+
+    #[test]
+    fn clears_are_named() {
+        let clear = LineClear { lines: 2, t_spin: TSpin::Full, combo: 0, back_to_back: false, perfect_clear: false, points: 0 };
+
+        assert_eq!(clear.name(), "T-SPIN DOUBLE");
+        assert_eq!(guideline_points(clear.lines, clear.t_spin), 1200);
+    }
+
+    fn line_clear(lines: u32) -> LineClear {
+        LineClear { lines, t_spin: TSpin::None, combo: 0, back_to_back: false, perfect_clear: false, points: 0 }
+    }
+
+    #[test]
+    fn classic_levels_up_once_the_goal_is_passed() {
+        let mut rules = Classic::default();
+
+        // a tetris counts 8, more than the 5 needed for level 2
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 1), 2);
+        // level 3 needs another 5 * 3
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 2), 2);
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 2), 3);
+    }
+
+    #[test]
+    fn nes_scores_by_level_and_levels_up_every_ten_lines() {
+        let mut rules = Nes::default();
+
+        assert_eq!(rules.start_level(), 0);
+        assert_eq!(rules.line_clear_points(&line_clear(4), 0), 1200);
+        assert_eq!(rules.line_clear_points(&line_clear(1), 9), 400);
+        assert_eq!(rules.hard_drop_points(10), 0);
+
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 0), 0);
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 0), 0);
+        assert_eq!(rules.level_after_lock(Some(&line_clear(2)), 0), 1);
+    }
+
+    #[test]
+    fn guideline_goal_carries_over() {
+        let mut rules = Guideline::default();
+
+        // a tetris is 8, 5 for level 1 and 3 left over
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 1), 2);
+        // 3 + 8 is more than the 10 needed for level 2
+        assert_eq!(rules.level_after_lock(Some(&line_clear(4)), 2), 3);
+        assert_eq!(rules.goal, 1);
+
+        // back-to-back tetrises count 12
+        let mut b2b = line_clear(4);
+        b2b.back_to_back = true;
+        assert_eq!(rules.level_after_lock(Some(&b2b), 3), 3);
+        assert_eq!(rules.goal, 13);
+        assert_eq!(rules.line_clear_points(&b2b, 3), 1200 * 3);
+    }
+
+    #[test]
+    fn guideline_levels_go_on_to_twenty_g() {
+        use crate::gravity::GravityCurve;
+
+        let mut rules = Guideline::default();
+        let mut level = rules.start_level();
+        for _ in 0..1000 {
+            if GravityCurve::Guideline.is_twenty_g(level) {
+                break;
+            }
+            level = rules.level_after_lock(Some(&line_clear(4)), level);
+        }
+
+        assert_eq!(level, 19);
+        // and there's no limit after that
+        for _ in 0..20 {
+            level = rules.level_after_lock(Some(&line_clear(4)), level);
+        }
+        assert!(level > 19);
+    }
+}